@@ -0,0 +1,48 @@
+//! Brings a running network to the state described in a fixture file.
+//!
+//! ```bash
+//! cargo run --example apply_fixture -- --fixture fixtures/looking_glass.yaml
+//! ```
+//!
+//! The fixture may be YAML or JSON, see `iroha_2_examples::fixture` for the
+//! format. Applying it again only reports what differs from the fixture.
+
+use eyre::Result;
+use iroha_2_examples::{
+    fixture::{apply, Fixture, FixtureReport},
+    harness::Args,
+};
+use iroha_client::client::Client;
+use iroha_config::client::Configuration;
+use std::{path::PathBuf, process::ExitCode};
+
+/// The fixture applied when `--fixture` isn't given.
+pub const DEFAULT_FIXTURE: &str =
+    concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/looking_glass.yaml");
+
+fn fixture_path(mut args: Args) -> Result<PathBuf> {
+    let mut path = PathBuf::from(DEFAULT_FIXTURE);
+    while let Some(flag) = args.next_flag()? {
+        match flag.as_str() {
+            "--fixture" => path = args.value()?.into(),
+            _ => return Err(Args::unexpected(&flag)),
+        }
+    }
+    Ok(path)
+}
+
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run_with_args("Fixture", fixture_path, |config, path| {
+        apply_fixture(config, &Fixture::from_path(path)?)?;
+        Ok(())
+    })
+}
+
+pub fn apply_fixture(config: &Configuration, fixture: &Fixture) -> Result<FixtureReport> {
+    let client: Client = Client::new(config)?;
+
+    let report = apply(&client, fixture)?;
+    println!("{report}");
+
+    Ok(report)
+}
@@ -0,0 +1,91 @@
+//! Streams committed blocks into a local archive.
+//!
+//! ```bash
+//! cargo run --example block_archive -- --archive blocks.jsonl --from 1
+//! # Later, carry on from the last archived block
+//! cargo run --example block_archive -- --archive blocks.jsonl --resume
+//! ```
+//!
+//! Without `--count`, the example keeps archiving new blocks until stopped.
+
+use eyre::Result;
+use iroha_2_examples::{
+    archive::{archive_blocks, BlockArchive},
+    harness::Args,
+};
+use iroha_client::client::Client;
+use iroha_config::client::Configuration;
+use std::{num::NonZeroU64, path::PathBuf, process::ExitCode};
+
+/// Command line options of the example.
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// The archive file.
+    pub archive: PathBuf,
+    /// Height of the first block to archive.
+    pub from: NonZeroU64,
+    /// Carry on from the last archived block instead of `from`.
+    pub resume: bool,
+    /// Stop after this many blocks.
+    pub count: Option<u64>,
+}
+
+impl Options {
+    fn from_args(mut args: Args) -> Result<Self> {
+        let mut options = Self {
+            archive: "./blocks.jsonl".into(),
+            from: NonZeroU64::new(1).expect("Not zero"),
+            resume: false,
+            count: None,
+        };
+        while let Some(flag) = args.next_flag()? {
+            match flag.as_str() {
+                "--archive" => options.archive = args.value()?.into(),
+                "--from" => options.from = args.parse()?,
+                "--count" => options.count = Some(args.parse()?),
+                "--resume" => options.resume = true,
+                _ => return Err(Args::unexpected(&flag)),
+            }
+        }
+        Ok(options)
+    }
+}
+
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run_with_args(
+        "Block archive",
+        Options::from_args,
+        block_archive_test,
+    )
+}
+
+pub fn block_archive_test(config: &Configuration, options: &Options) -> Result<()> {
+    let client: Client = Client::new(config)?;
+
+    // Open the archive and find out where to start
+    let mut archive = BlockArchive::open(&options.archive)?;
+    let from = if options.resume {
+        archive.next_height()
+    } else {
+        if archive.tip().is_some() {
+            // Appending from an arbitrary height would break the chain
+            return Err(eyre::eyre!(
+                "`{}` isn't empty, pass `--resume` to carry on",
+                archive.path().display()
+            ));
+        }
+        options.from
+    };
+    println!("Archiving blocks from height {from}");
+
+    // Stream the blocks, checking that each one extends the previous
+    let archived = archive_blocks(&client, &mut archive, from, options.count, |tip| {
+        println!("Archived block {} ({})", tip.height, tip.hash);
+    })?;
+    println!(
+        "Archived {archived} block(s), the last one is {:?}",
+        archive.tip()
+    );
+
+    Ok(())
+}
@@ -6,7 +6,7 @@ fn main() {
     println!("Account definition example works!");
 }
 
-fn account_definition_test() -> Result<(), Error> {
+pub fn account_definition_test() -> Result<(), Error> {
     // #region account_definition_comparison
     use iroha_data_model::prelude::AccountId;
 
@@ -1,51 +1,31 @@
-use eyre::{Error, WrapErr};
+use eyre::Error;
+use iroha_2_examples::keys;
 use iroha_config::client::Configuration;
-use std::fs::File;
+use iroha_data_model::prelude::{AccountId, PublicKey};
+use std::process::ExitCode;
 
-fn main() {
-    let config = load_configuration().expect("Configuration should be loading normally");
-    account_registration_test(&config)
-        .expect("Account registration example is expected to work correctly");
-    println!("Account registration example works!");
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run("Account registration", |config| {
+        account_registration_test(config)
+    })
 }
 
-fn load_configuration() -> Result<Configuration, Error> {
-    // #region rust_config_load
-    let config_loc = "./config.json";
-    let file = File::open(config_loc)
-        .wrap_err(format!(
-            "Unable to load the configuration file at `{}`",
-            config_loc
-        ))
-        .expect("Config file is loading normally.");
-    let config: Configuration = serde_json::from_reader(file)
-        .wrap_err(format!("Failed to parse `{}`", config_loc))
-        .expect("Verified in tests");
-    // #endregion rust_config_load
-
-    // Return the configuration normally
-    Ok(config)
-}
-
-// This function imitates the user sharing a public key.
-// It generates a new public key for a new account.
-// Normally, it is generated by Kagami.
-fn get_key_from_white_rabbit() -> iroha_crypto::PublicKey {
-    use iroha_crypto::KeyPair;
-
-    let (public_key, _) = KeyPair::generate()
-        .expect("Failed to generate KeyPair")
-        .into();
-
-    public_key
+// This function imitates the white_rabbit sharing a public key.
+// The key pair is derived from the same seed as in the tutorials,
+// so they can sign transactions as the white_rabbit later.
+fn get_key_from_white_rabbit() -> Result<PublicKey, Error> {
+    let (public_key, _) = keys::from_seed(keys::WHITE_RABBIT_SEED)?.into();
+    Ok(public_key)
 }
 
-fn account_registration_test(config: &Configuration) -> Result<(), Error> {
+pub fn account_registration_test(config: &Configuration) -> Result<(), Error> {
     // #region register_account_crates
+    use iroha_2_examples::status::{submit, Outcome};
     use iroha_client::client::Client;
     use iroha_data_model::{
         metadata::UnlimitedMetadata,
-        prelude::{Account, AccountId, InstructionBox, RegisterBox},
+        prelude::{Account, InstructionBox, RegisterBox},
+        query::account::model::FindAccountById,
     };
     // #endregion register_account_crates
 
@@ -60,11 +40,11 @@ fn account_registration_test(config: &Configuration) -> Result<(), Error> {
     // #endregion register_account_create
 
     // Use a public key that was shared by the white_rabbit.
-    let public_key = get_key_from_white_rabbit();
+    let public_key = get_key_from_white_rabbit()?;
 
     // #region register_account_generate
     // Generate a new account
-    let create_account = RegisterBox::new(Account::new(account_id, [public_key]));
+    let create_account = RegisterBox::new(Account::new(account_id.clone(), [public_key]));
     // #endregion register_account_generate
 
     // #region register_account_prepare_tx
@@ -77,9 +57,20 @@ fn account_registration_test(config: &Configuration) -> Result<(), Error> {
 
     // #region register_account_submit_tx
     // Submit a prepared account registration transaction
-    iroha_client.submit_transaction(&tx)?;
+    // and wait for its outcome
+    let outcome = submit(&iroha_client, config, &tx)?.wait()?;
+    println!("Account registration transaction {outcome}");
+    assert!(matches!(outcome, Outcome::Committed));
     // #endregion register_account_submit_tx
 
+    // #region register_account_verify
+    // Check that the account exists now
+    let account = iroha_client.request(FindAccountById {
+        id: account_id.into(),
+    })?;
+    println!("Registered account: {account:?}");
+    // #endregion register_account_verify
+
     // Finish the test successfully
     Ok(())
 }
@@ -1,36 +1,16 @@
 use eyre::{Error, WrapErr};
 use iroha_config::client::Configuration;
-use std::fs::File;
+use std::process::ExitCode;
 
-fn main() {
-    let config = load_configuration().expect("Configuration should be loading normally");
-    asset_minting_test(&config).expect("Asset minting example is expected to work correctly");
-
-    println!("Asset minting example works!");
-}
-
-fn load_configuration() -> Result<Configuration, Error> {
-    // #region rust_config_load
-    let config_loc = "./config.json";
-    let file = File::open(config_loc)
-        .wrap_err(format!(
-            "Unable to load the configuration file at `{}`",
-            config_loc
-        ))
-        .expect("Config file is loading normally.");
-    let config: Configuration = serde_json::from_reader(file)
-        .wrap_err(format!("Failed to parse `{}`", config_loc))
-        .expect("Verified in tests");
-    // #endregion rust_config_load
-
-    // Return the configuration normally
-    Ok(config)
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run("Asset minting", asset_minting_test)
 }
 
-fn asset_minting_test(config: &Configuration) -> Result<(), Error> {
+pub fn asset_minting_test(config: &Configuration) -> Result<(), Error> {
     // #region mint_asset_crates
     use std::str::FromStr;
 
+    use iroha_2_examples::status::submit_instructions;
     use iroha_client::client::Client;
     use iroha_data_model::{
         prelude::{AccountId, AssetDefinitionId, AssetId, MintBox, ToValue},
@@ -58,9 +38,9 @@ fn asset_minting_test(config: &Configuration) -> Result<(), Error> {
     // #endregion mint_asset_mint
 
     // #region mint_asset_submit_tx
-    iroha_client
-        .submit(mint_roses)
-        .wrap_err("Failed to submit transaction")?;
+    submit_instructions(&iroha_client, config, [mint_roses.into()])
+        .wrap_err("Failed to submit transaction")?
+        .wait_committed()?;
     // #endregion mint_asset_submit_tx
 
     // #region mint_asset_mint_alt
@@ -76,9 +56,9 @@ fn asset_minting_test(config: &Configuration) -> Result<(), Error> {
     // #endregion mint_asset_mint_alt
 
     // #region mint_asset_submit_tx_alt
-    iroha_client
-        .submit(mint_roses_alt)
-        .wrap_err("Failed to submit transaction")?;
+    submit_instructions(&iroha_client, config, [mint_roses_alt.into()])
+        .wrap_err("Failed to submit transaction")?
+        .wait_committed()?;
     // #endregion mint_asset_submit_tx_alt
 
     // Finish the test successfully
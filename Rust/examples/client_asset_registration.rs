@@ -1,38 +1,17 @@
-use eyre::{Error, WrapErr};
+use eyre::Error;
 use iroha_config::client::Configuration;
 use iroha_data_model::TryToValue;
-use std::fs::File;
+use std::process::ExitCode;
 
-fn main() {
-    let config = load_configuration().expect("Configuration should be loading normally");
-    asset_registration_test(&config)
-        .expect("Asset registration example is expected to work correctly");
-
-    println!("Asset registration example works!");
-}
-
-fn load_configuration() -> Result<Configuration, Error> {
-    // #region rust_config_load
-    let config_loc = "./config.json";
-    let file = File::open(config_loc)
-        .wrap_err(format!(
-            "Unable to load the configuration file at `{}`",
-            config_loc
-        ))
-        .expect("Config file is loading normally.");
-    let config: Configuration = serde_json::from_reader(file)
-        .wrap_err(format!("Failed to parse `{}`", config_loc))
-        .expect("Verified in tests");
-    // #endregion rust_config_load
-
-    // Return the configuration normally
-    Ok(config)
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run("Asset registration", asset_registration_test)
 }
 
-fn asset_registration_test(config: &Configuration) -> Result<(), Error> {
+pub fn asset_registration_test(config: &Configuration) -> Result<(), Error> {
     // #region register_asset_crates
     use std::str::FromStr as _;
 
+    use iroha_2_examples::status::submit_instructions;
     use iroha_client::client::Client;
     use iroha_data_model::prelude::{
         AccountId, AssetDefinition, AssetDefinitionId, AssetId, IdBox, MintBox, RegisterBox,
@@ -49,12 +28,13 @@ fn asset_registration_test(config: &Configuration) -> Result<(), Error> {
     // #endregion register_asset_create_asset
 
     // #region register_asset_init_submit
-    // Initialise the registration time
+    // Initialise the registration time.
+    // It is mintable once, so the mint below fixes its supply.
     let register_time =
         RegisterBox::new(AssetDefinition::fixed(asset_def_id.clone()).mintable_once());
 
-    // Submit a registration time
-    iroha_client.submit(register_time)?;
+    // Submit a registration time and wait for it to be committed
+    submit_instructions(&iroha_client, config, [register_time.into()])?.wait_committed()?;
     // #endregion register_asset_init_submit
 
     // Create an account using the previously defined asset
@@ -69,8 +49,8 @@ fn asset_registration_test(config: &Configuration) -> Result<(), Error> {
         IdBox::AssetId(AssetId::new(asset_def_id, account_id)),
     );
 
-    // Submit a minting transaction
-    iroha_client.submit_all([mint])?;
+    // Submit a minting transaction and wait for it to be committed
+    submit_instructions(&iroha_client, config, [mint.into()])?.wait_committed()?;
     // #endregion register_asset_mint_submit
 
     // Finish the test successfully
@@ -0,0 +1,84 @@
+use eyre::Error;
+use iroha_config::client::Configuration;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run("Batch submission", batch_submission_test)
+}
+
+pub fn batch_submission_test(config: &Configuration) -> Result<(), Error> {
+    // #region batch_submission_crates
+    use std::num::NonZeroUsize;
+
+    use iroha_2_examples::{
+        batch::{BatchSubmitter, Mode},
+        query::PagedQuery,
+    };
+    use iroha_client::client::Client;
+    use iroha_data_model::{
+        prelude::{Account, AccountId, Domain, DomainId, InstructionBox, RegisterBox},
+        query::account::model::FindAccountsByDomainId,
+    };
+    // #endregion batch_submission_crates
+
+    // Create an Iroha client
+    let iroha_client: Client = Client::new(&config)?;
+
+    // #region batch_submission_domain
+    // The accounts need their domain to be registered first
+    let domain_id: DomainId = "batch_land".parse()?;
+    iroha_client.submit_blocking(RegisterBox::new(Domain::new(domain_id.clone())))?;
+    // #endregion batch_submission_domain
+
+    // #region batch_submission_instructions
+    // Prepare many independent instructions. They are generated
+    // lazily, so the whole set never has to be in memory.
+    let count = 1000;
+    let registrations = (0..count).map(|i| -> InstructionBox {
+        let account_id = AccountId::new(
+            format!("worker_{i}").parse().expect("Valid"),
+            domain_id.clone(),
+        );
+        RegisterBox::new(Account::new(account_id, [])).into()
+    });
+    // #endregion batch_submission_instructions
+
+    // #region batch_submission_submit
+    // Pack them into transactions. `TRANSACTION_LIMITS.max_instruction_number`
+    // allows 4096 instructions per transaction; smaller batches are used here
+    // to show several of them, submitted from four threads.
+    let submitter = BatchSubmitter::new(iroha_client.clone(), &config.transaction_limits)?
+        .max_batch_size(NonZeroUsize::new(300).expect("Not zero"))
+        .mode(Mode::Concurrent(NonZeroUsize::new(4).expect("Not zero")));
+    let results = submitter.submit(registrations);
+    // #endregion batch_submission_submit
+
+    // #region batch_submission_results
+    // Every batch reports its own outcome
+    for result in &results {
+        match &result.outcome {
+            Ok(hash) => println!(
+                "Batch {} with {} instruction(s) committed in {hash}",
+                result.index, result.instructions
+            ),
+            Err(error) => println!("Batch {} failed: {error:?}", result.index),
+        }
+    }
+    assert_eq!(results.len(), 4);
+    assert!(results.iter().all(|result| result.outcome.is_ok()));
+    // #endregion batch_submission_results
+
+    // #region batch_submission_verify
+    // All the accounts are there
+    let accounts = PagedQuery::new(FindAccountsByDomainId {
+        domain_id: domain_id.into(),
+    })
+    .iter::<Account>(&iroha_client)
+    .collect::<Result<Vec<_>, _>>()?
+    .len();
+    assert_eq!(accounts, count);
+    // #endregion batch_submission_verify
+
+    // Finish the test successfully
+    Ok(())
+}
@@ -1,39 +1,19 @@
 use eyre::{Error, WrapErr};
 use iroha_config::client::Configuration;
-use std::fs::File;
+use std::process::ExitCode;
 
-fn main() {
-    let config = load_configuration().expect("Configuration should be loading normally");
-    domain_registration_test(&config)
-        .expect("Domain registration example is expected to work correctly");
-
-    println!("Domain registration example works!");
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run("Domain registration", domain_registration_test)
 }
 
-fn load_configuration() -> Result<Configuration, Error> {
-    // #region rust_config_load
-    let config_loc = "./config.json";
-    let file = File::open(config_loc)
-        .wrap_err(format!(
-            "Unable to load the configuration file at `{}`",
-            config_loc
-        ))
-        .expect("Config file is loading normally.");
-    let config: Configuration = serde_json::from_reader(file)
-        .wrap_err(format!("Failed to parse `{}`", config_loc))
-        .expect("Verified in tests");
-    // #endregion rust_config_load
-
-    // Return the configuration normally
-    Ok(config)
-}
-
-fn domain_registration_test(config: &Configuration) -> Result<(), Error> {
+pub fn domain_registration_test(config: &Configuration) -> Result<(), Error> {
     // #region domain_register_example_crates
+    use iroha_2_examples::status::{submit, Outcome};
     use iroha_client::client::Client;
     use iroha_data_model::{
         metadata::UnlimitedMetadata,
         prelude::{Domain, DomainId, InstructionBox, RegisterBox},
+        query::domain::model::FindDomainById,
     };
     // #endregion domain_register_example_crates
 
@@ -44,7 +24,7 @@ fn domain_registration_test(config: &Configuration) -> Result<(), Error> {
 
     // #region domain_register_example_create_isi
     // Create an ISI
-    let create_looking_glass = RegisterBox::new(Domain::new(looking_glass));
+    let create_looking_glass = RegisterBox::new(Domain::new(looking_glass.clone()));
     // #endregion domain_register_example_create_isi
 
     // #region rust_client_create
@@ -63,11 +43,22 @@ fn domain_registration_test(config: &Configuration) -> Result<(), Error> {
 
     // #region domain_register_example_submit_tx
     // Submit a prepared domain registration transaction
-    iroha_client
-        .submit_transaction(&tx)
-        .wrap_err("Failed to submit transaction")?;
+    // and wait for its outcome
+    let outcome = submit(&iroha_client, config, &tx)
+        .wrap_err("Failed to submit transaction")?
+        .wait()?;
+    println!("Domain registration transaction {outcome}");
+    assert!(matches!(outcome, Outcome::Committed));
     // #endregion domain_register_example_submit_tx
 
+    // #region domain_register_example_verify
+    // Check that the domain exists now
+    let domain = iroha_client.request(FindDomainById {
+        id: looking_glass.into(),
+    })?;
+    println!("Registered domain: {domain:?}");
+    // #endregion domain_register_example_verify
+
     // Finish the test successfully
     Ok(())
 }
@@ -0,0 +1,90 @@
+use eyre::{Error, WrapErr};
+use iroha_config::client::Configuration;
+use std::{process::ExitCode, time::Duration};
+
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run("Event subscription", event_subscription_test)
+}
+
+pub fn event_subscription_test(config: &Configuration) -> Result<(), Error> {
+    // #region event_subscription_crates
+    use futures::StreamExt;
+    use iroha_2_examples::events::{
+        asset_changes_in_domain, is_final, transaction_status, EventListener,
+    };
+    use iroha_client::client::Client;
+    use iroha_data_model::{
+        metadata::UnlimitedMetadata,
+        prelude::{
+            AccountId, AssetDefinitionId, AssetId, DomainId, Event, IdBox, InstructionBox, MintBox,
+            PipelineStatus, ToValue,
+        },
+    };
+    // #endregion event_subscription_crates
+
+    // Create an Iroha client
+    let iroha_client: Client = Client::new(&config)?;
+
+    // Wait for the events on a runtime with a timer,
+    // so that a missing event fails the example instead of hanging it
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()?;
+    let timeout = Duration::from_millis(config.transaction_status_timeout_ms);
+
+    // #region event_subscription_data_filter
+    // Subscribe to the changes of every asset held in `wonderland`
+    let wonderland: DomainId = "wonderland".parse()?;
+    let mut asset_events =
+        EventListener::new(iroha_client.clone(), asset_changes_in_domain(wonderland))
+            .into_stream()?;
+    // #endregion event_subscription_data_filter
+
+    // #region event_subscription_prepare_tx
+    // Prepare a transaction minting some roses for Alice
+    let roses: AssetDefinitionId = "rose#wonderland".parse()?;
+    let alice: AccountId = "alice@wonderland".parse()?;
+    let mint_roses = MintBox::new(7_u32.to_value(), IdBox::AssetId(AssetId::new(roses, alice)));
+    let instructions: Vec<InstructionBox> = vec![mint_roses.into()];
+    let tx = iroha_client.build_transaction(instructions, UnlimitedMetadata::new())?;
+    // #endregion event_subscription_prepare_tx
+
+    // #region event_subscription_pipeline_filter
+    // Subscribe to the status of the transaction before submitting it,
+    // so that no status change is missed
+    let mut statuses =
+        EventListener::new(iroha_client.clone(), transaction_status(tx.hash())).into_stream()?;
+    iroha_client
+        .submit_transaction(&tx)
+        .wrap_err("Failed to submit transaction")?;
+
+    // Wait for the transaction to be committed or rejected
+    let status = runtime
+        .block_on(tokio::time::timeout(timeout, async {
+            while let Some(event) = statuses.next().await {
+                if let Event::Pipeline(event) = event? {
+                    println!("Transaction status: {:?}", event.status);
+                    if is_final(&event) {
+                        return Ok(event.status);
+                    }
+                }
+            }
+            Err(eyre::eyre!("The status stream ended early"))
+        }))
+        .wrap_err("No final status before the timeout")??;
+    assert!(matches!(status, PipelineStatus::Committed));
+    // #endregion event_subscription_pipeline_filter
+
+    // #region event_subscription_receive
+    // The committed mint shows up as an asset event
+    let event = runtime
+        .block_on(tokio::time::timeout(timeout, asset_events.next()))
+        .wrap_err("No asset event before the timeout")?
+        .ok_or_else(|| eyre::eyre!("The asset event stream ended early"))??;
+    assert!(matches!(event, Event::Data(_)));
+    println!("Asset event: {event:?}");
+    // #endregion event_subscription_receive
+
+    // Finish the test successfully
+    Ok(())
+}
@@ -0,0 +1,71 @@
+//! Keeps the key pairs of new accounts in a key store and signs as them.
+//!
+//! ```bash
+//! IROHA_KEY_STORE=./keys cargo run --example client_key_store
+//! ```
+
+use eyre::Result;
+use iroha_2_examples::{
+    ensure::ensure_account,
+    keys::{self, KeyStore},
+};
+use iroha_client::client::Client;
+use iroha_config::client::Configuration;
+use iroha_crypto::Algorithm;
+use iroha_data_model::{prelude::*, query::account::model::FindAccountById};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run("Key store", |config| {
+        key_store_test(config, &KeyStore::from_env()?)
+    })
+}
+
+pub fn key_store_test(config: &Configuration, store: &KeyStore) -> Result<()> {
+    // Key pairs can be generated for every supported algorithm.
+    // The public keys are multihashes, like `PUBLIC_KEY` in `config.json`.
+    for algorithm in [Algorithm::Ed25519, Algorithm::Secp256k1] {
+        let key_pair = keys::generate(algorithm)?;
+        println!("A {algorithm} public key: {}", key_pair.public_key());
+    }
+
+    // The mad hatter's key pair is stored under his account id,
+    // or loaded if an earlier run stored it already
+    let mad_hatter: AccountId = "mad_hatter@wonderland".parse()?;
+    let key_pair = store.load_or_generate(&mad_hatter.to_string())?;
+    println!(
+        "The mad hatter's public key: {}",
+        store.export_public_key(&mad_hatter.to_string())?
+    );
+
+    // Alice registers the account with the public key
+    let alice_client = Client::new(config)?;
+    let ensured = ensure_account(
+        &alice_client,
+        Account::new(mad_hatter.clone(), [key_pair.public_key().clone()]),
+    )?;
+    println!("Account `{mad_hatter}` {ensured}");
+
+    // Later, the mad hatter loads his key pair from the store and signs
+    // a transaction setting his own metadata
+    let hatter_client = Client::new(&store.signing_as(config, mad_hatter.clone())?)?;
+    let hat: Name = "hat".parse()?;
+    let set_hat: InstructionBox = SetKeyValueBox::new(
+        IdBox::AccountId(mad_hatter.clone()),
+        hat.clone(),
+        Value::String("top hat".to_owned()),
+    )
+    .into();
+    hatter_client.submit_blocking(set_hat)?;
+
+    let account = alice_client.request(FindAccountById {
+        id: mad_hatter.into(),
+    })?;
+    assert_eq!(
+        account.metadata.get(&hat),
+        Some(&Value::String("top hat".to_owned()))
+    );
+    println!("The mad hatter signed his own transaction");
+
+    Ok(())
+}
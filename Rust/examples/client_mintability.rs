@@ -0,0 +1,100 @@
+//! What the mintability of an asset definition guarantees.
+//!
+//! - `Infinitely`: the asset can be minted any number of times.
+//! - `Once`: the asset can be minted once, so its supply is fixed after that.
+//! - `Not`: the asset can't be minted at all.
+//!
+//! The example can be run again on the same network: gold and relics are
+//! kept from the earlier runs, and medals get a new definition every run,
+//! since the medals of an earlier run can't be minted anymore.
+
+use eyre::Result;
+use iroha_2_examples::{
+    ensure::ensure_asset_definition,
+    rejection::Rejection,
+    status::{submit_instructions, Outcome},
+};
+use iroha_client::client::Client;
+use iroha_config::client::Configuration;
+use iroha_data_model::prelude::*;
+use std::{
+    process::ExitCode,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run("Mintability", |config| {
+        mintability_test(config)?;
+        Ok(())
+    })
+}
+
+/// Register `definition` unless it is there from an earlier run.
+fn register(client: &Client, definition: NewAssetDefinition) -> Result<()> {
+    let id = definition.id.clone();
+    let ensured = ensure_asset_definition(client, definition)?;
+    println!("Asset definition `{id}` {ensured}");
+    Ok(())
+}
+
+/// Mint `amount` of `definition` for Alice and wait for the outcome.
+fn mint(
+    client: &Client,
+    config: &Configuration,
+    definition: &AssetDefinitionId,
+    amount: u32,
+) -> Result<Outcome> {
+    let alice = AccountId::from_str("alice@wonderland")?;
+    let asset = AssetId::new(definition.clone(), alice);
+    let mint = MintBox::new(amount.to_value(), IdBox::AssetId(asset));
+    submit_instructions(client, config, [mint.into()])?.wait()
+}
+
+/// Returns the rejections of the second mint of a once-mintable asset
+/// and of the mint of a non-mintable asset.
+pub fn mintability_test(config: &Configuration) -> Result<[Rejection; 2]> {
+    let client: Client = Client::new(config)?;
+
+    // Gold can be minted whenever needed
+    let gold = AssetDefinitionId::from_str("gold#wonderland")?;
+    register(&client, AssetDefinition::quantity(gold.clone()))?;
+    for _ in 0..2 {
+        assert!(matches!(
+            mint(&client, config, &gold, 10)?,
+            Outcome::Committed
+        ));
+    }
+    println!("Gold was minted twice");
+
+    // Medals are minted once, then their supply is fixed.
+    // A new kind of medal every run, so the first mint is the first one.
+    let run = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+    let medal = AssetDefinitionId::from_str(&format!("medal_{run}#wonderland"))?;
+    register(
+        &client,
+        AssetDefinition::quantity(medal.clone()).mintable_once(),
+    )?;
+    assert!(matches!(
+        mint(&client, config, &medal, 3)?,
+        Outcome::Committed
+    ));
+    let outcome = mint(&client, config, &medal, 1)?;
+    let second_medal_mint = outcome
+        .rejection()
+        .ok_or_else(|| eyre::eyre!("The second mint of medals was {outcome}"))?;
+    println!("Minting medals again: {second_medal_mint}");
+
+    // Relics can't be minted at all
+    let relic = AssetDefinitionId::from_str("relic#wonderland")?;
+    let mut not_mintable = AssetDefinition::quantity(relic.clone());
+    not_mintable.mintable = Mintable::Not;
+    register(&client, not_mintable)?;
+    let outcome = mint(&client, config, &relic, 1)?;
+    let relic_mint = outcome
+        .rejection()
+        .ok_or_else(|| eyre::eyre!("The mint of relics was {outcome}"))?;
+    println!("Minting relics: {relic_mint}");
+
+    Ok([second_medal_mint, relic_mint])
+}
@@ -0,0 +1,60 @@
+//! Alice and the white rabbit, both played by one program.
+//!
+//! Alice registers the white rabbit and gives him some roses, then the
+//! white rabbit signs a transfer of some of them back to Alice.
+
+use eyre::Result;
+use iroha_2_examples::{
+    ensure::{ensure_account, ensure_domain, Ensured},
+    identities::Identities,
+    keys,
+    transfer::transfer,
+};
+use iroha_config::client::Configuration;
+use iroha_data_model::prelude::*;
+use std::{process::ExitCode, str::FromStr};
+
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run("Multiple identities", |config| {
+        multiple_identities_test(config)
+    })
+}
+
+pub fn multiple_identities_test(config: &Configuration) -> Result<()> {
+    // Alice is the account of `config.json`
+    let mut identities = Identities::new(config)?;
+    let alice = identities.default_account().clone();
+    let alice_client = identities.client(&alice)?;
+
+    // The white rabbit's key pair comes from the seed of the tutorials,
+    // so he keeps the same key across runs and examples
+    let white_rabbit = AccountId::from_str("white_rabbit@looking_glass")?;
+    let key_pair = keys::from_seed(keys::WHITE_RABBIT_SEED)?;
+
+    // Alice registers the white rabbit, unless an earlier run did
+    ensure_domain(&alice_client, Domain::new(white_rabbit.domain_id.clone()))?;
+    let ensured = ensure_account(
+        &alice_client,
+        Account::new(white_rabbit.clone(), [key_pair.public_key().clone()]),
+    )?;
+    if let Ensured::Differs(_) = ensured {
+        return Err(eyre::eyre!(
+            "`{white_rabbit}` {ensured}, so the seeded key pair can't sign for him"
+        ));
+    }
+    identities.add(white_rabbit.clone(), key_pair);
+
+    // Alice gives the white rabbit 5 roses
+    let rose = AssetDefinitionId::from_str("rose#wonderland")?;
+    let report = transfer(&alice_client, &rose, &alice, &white_rabbit, 5_u32)?;
+    println!("Alice gave the white rabbit 5 roses: {report:?}");
+
+    // The white rabbit signs a transfer of 2 of them back
+    let white_rabbit_client = identities.client(&white_rabbit)?;
+    let report = transfer(&white_rabbit_client, &rose, &white_rabbit, &alice, 2_u32)?;
+    assert_eq!(report.sender_after, report.sender_before - 2);
+    assert_eq!(report.receiver_after, report.receiver_before + 2);
+    println!("The white rabbit gave Alice 2 roses back: {report:?}");
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+//! An account shared by three signatories, any two of which must sign.
+//!
+//! The account is registered with three public keys and a 2-of-3 signature
+//! check condition. A transaction signed with one key waits in the queue;
+//! the same transaction signed with a second key is committed.
+//!
+//! Every run registers a new shared account, so the example can be run
+//! again on the same network.
+
+use eyre::Result;
+use iroha_2_examples::{
+    keys, metadata,
+    multisig::{cosign, m_of_n, set_condition},
+    status::{submit, submit_instructions, Outcome},
+};
+use iroha_client::client::Client;
+use iroha_config::client::Configuration;
+use iroha_crypto::{Algorithm, KeyPair};
+use iroha_data_model::{metadata::UnlimitedMetadata, prelude::*};
+use std::{
+    process::ExitCode,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run("Multi-signature account", multisig_test)
+}
+
+pub fn multisig_test(config: &Configuration) -> Result<()> {
+    let alice_client: Client = Client::new(config)?;
+
+    // The three signatories each keep their own key pair
+    let key_pairs: Vec<KeyPair> = (0..3)
+        .map(|_| keys::generate(Algorithm::Ed25519))
+        .collect::<Result<_>>()?;
+    let public_keys: Vec<PublicKey> = key_pairs
+        .iter()
+        .map(|key_pair| key_pair.public_key().clone())
+        .collect();
+
+    // Alice registers the shared account with all three keys.
+    // The keys are new every run, and so is the account.
+    let run = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+    let tea_party = AccountId::from_str(&format!("tea_party_{run}@wonderland"))?;
+    let register = RegisterBox::new(Account::new(tea_party.clone(), public_keys.clone()));
+    submit_instructions(&alice_client, config, [register.into()])?.wait_committed()?;
+
+    // Each signatory has a client signing as the shared account with their key
+    let signatory_configs: Vec<Configuration> = key_pairs
+        .iter()
+        .map(|key_pair| keys::signing_as(config, tea_party.clone(), key_pair.clone()))
+        .collect();
+    let signatories: Vec<Client> = signatory_configs
+        .iter()
+        .map(Client::new)
+        .collect::<Result<_>>()?;
+
+    // Until the condition is set, one signature is enough, so the first
+    // signatory sets it on behalf of the account
+    let condition = m_of_n(&public_keys, 2)?;
+    let set = set_condition(tea_party.clone(), condition);
+    submit_instructions(&signatories[0], &signatory_configs[0], [set])?.wait_committed()?;
+
+    // From now on, a transaction of the account needs two signatures.
+    // The first signatory builds and signs it.
+    let guest = Name::from_str("guest_of_honour")?;
+    let invite: InstructionBox = SetKeyValueBox::new(
+        IdBox::AccountId(tea_party.clone()),
+        guest.clone(),
+        Value::String("Alice".to_owned()),
+    )
+    .into();
+    let transaction = signatories[0].build_transaction([invite], UnlimitedMetadata::new())?;
+
+    // With one signature, the transaction waits for more in the queue.
+    // Give up waiting sooner than usual to show it.
+    let mut impatient = signatory_configs[0].clone();
+    impatient.transaction_status_timeout_ms = 3_000;
+    let outcome = submit(&signatories[0], &impatient, &transaction)?.wait()?;
+    println!("With one signature: {outcome}");
+    assert!(matches!(outcome, Outcome::TimedOut));
+
+    // The second signatory adds a signature to the same transaction
+    // and submits it again, which commits it
+    let transaction = cosign(transaction, key_pairs[1].clone())?;
+    let outcome = submit(&signatories[1], &signatory_configs[1], &transaction)?.wait()?;
+    println!("With two signatures: {outcome}");
+    assert!(matches!(outcome, Outcome::Committed));
+
+    // Any signatory can read the result
+    let guest_of_honour: Option<String> = metadata::get(&signatories[2], tea_party, &guest)?;
+    assert_eq!(guest_of_honour.as_deref(), Some("Alice"));
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use eyre::Error;
+use iroha_config::client::Configuration;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run("Paginated queries", paginated_queries_test)
+}
+
+pub fn paginated_queries_test(config: &Configuration) -> Result<(), Error> {
+    // #region paginated_queries_crates
+    use std::num::NonZeroU32;
+
+    use iroha_2_examples::{ensure::ensure_domain, query::PagedQuery};
+    use iroha_client::client::Client;
+    use iroha_data_model::{
+        metadata::Limits as MetadataLimits,
+        predicate::{string, value, PredicateBox},
+        prelude::{Domain, DomainId, Metadata, Name, Value},
+        query::domain::model::FindAllDomains,
+    };
+    // #endregion paginated_queries_crates
+
+    // Create an Iroha client
+    let iroha_client: Client = Client::new(&config)?;
+
+    // #region paginated_queries_register
+    // Register a few domains, ranking them in the reverse order of their names,
+    // unless an earlier run registered them
+    let rank: Name = "rank".parse()?;
+    let count = 7_u32;
+    for i in 0..count {
+        let domain_id: DomainId = format!("page_{i}").parse()?;
+        let mut metadata = Metadata::new();
+        metadata.insert_with_limits(
+            rank.clone(),
+            Value::from(count - i),
+            MetadataLimits::new(16, 1024),
+        )?;
+        let ensured = ensure_domain(
+            &iroha_client,
+            Domain::new(domain_id.clone()).with_metadata(metadata),
+        )?;
+        println!("Domain `{domain_id}` {ensured}");
+    }
+    // #endregion paginated_queries_register
+
+    // #region paginated_queries_pages
+    // Fetch the domains three at a time.
+    // Every page but the last one is full.
+    let page_size = NonZeroU32::new(3).expect("Not zero");
+    let mut pages = PagedQuery::new(FindAllDomains)
+        .page_size(page_size)
+        .iter(&iroha_client);
+    let mut total = 0;
+    while let Some(page) = pages.next_page() {
+        let page: Vec<Domain> = page?;
+        println!(
+            "Page {}: {:?}",
+            pages.pages_fetched(),
+            page.iter()
+                .map(|domain| domain.id.to_string())
+                .collect::<Vec<_>>()
+        );
+        assert!(page.len() <= 3);
+        total += page.len();
+    }
+    // The genesis domains are there too
+    assert!(total >= count as usize);
+    // #endregion paginated_queries_pages
+
+    // #region paginated_queries_filter_sort
+    // Only take the domains registered above, ordered by their rank.
+    // The peer filters before paginating, so the pages only hold matches.
+    let only_pages = PredicateBox::new(value::ValuePredicate::Identifiable(
+        string::StringPredicate::starts_with("page_"),
+    ));
+    let ranked = PagedQuery::new(FindAllDomains)
+        .page_size(page_size)
+        .filter(only_pages)
+        .sort_by_metadata_key(rank.clone())
+        .iter(&iroha_client)
+        .collect::<Result<Vec<Domain>, _>>()?;
+
+    let names: Vec<String> = ranked.iter().map(|domain| domain.id.to_string()).collect();
+    println!("Domains by rank: {names:?}");
+    assert_eq!(names.len(), count as usize);
+    // The order is ascending and `page_0` has the highest rank
+    assert_eq!(names.first().map(String::as_str), Some("page_6"));
+    assert_eq!(names.last().map(String::as_str), Some("page_0"));
+    // #endregion paginated_queries_filter_sort
+
+    // #region paginated_queries_lazy
+    // Stopping early only fetches the pages it needs
+    let mut lazy = PagedQuery::new(FindAllDomains)
+        .page_size(page_size)
+        .iter(&iroha_client);
+    let first = lazy.next().transpose()?;
+    println!(
+        "First domain: {:?}, pages fetched: {}",
+        first.map(|domain| domain.id.clone()),
+        lazy.pages_fetched()
+    );
+    assert_eq!(lazy.pages_fetched(), 1);
+    // #endregion paginated_queries_lazy
+
+    // Finish the test successfully
+    Ok(())
+}
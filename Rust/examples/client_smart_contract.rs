@@ -0,0 +1,54 @@
+use eyre::Error;
+use iroha_config::client::Configuration;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run("Smart contract", smart_contract_test)
+}
+
+pub fn smart_contract_test(config: &Configuration) -> Result<(), Error> {
+    // #region smart_contract_crates
+    use iroha_2_examples::{transfer::balance, wasm};
+    use iroha_client::client::Client;
+    use iroha_data_model::prelude::{AccountId, AssetDefinitionId};
+    // #endregion smart_contract_crates
+
+    // Create an Iroha client
+    let iroha_client: Client = Client::new(&config)?;
+
+    // #region smart_contract_build
+    // Compile the smart contract crate to WASM
+    let smart_contract = wasm::build(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/smart_contracts/mint_rose"
+    ))?;
+    // #endregion smart_contract_build
+
+    // #region smart_contract_check_size
+    // Make sure the peer accepts a transaction this large
+    let size = wasm::check_size(&smart_contract, &config.transaction_limits)?;
+    println!(
+        "Smart contract size: {size} of {} bytes allowed",
+        config.transaction_limits.max_wasm_size_bytes
+    );
+    // #endregion smart_contract_check_size
+
+    // #region smart_contract_submit
+    // The smart contract mints a rose for the account that submits it
+    let alice: AccountId = "alice@wonderland".parse()?;
+    let roses: AssetDefinitionId = "rose#wonderland".parse()?;
+    let roses_before = balance::<u32>(&iroha_client, &roses, &alice)?;
+
+    let hash = wasm::submit(&iroha_client, config, smart_contract)?;
+    println!("Smart contract committed in transaction {hash}");
+    // #endregion smart_contract_submit
+
+    // #region smart_contract_verify
+    // Check the side effect of the smart contract
+    let roses_after = balance::<u32>(&iroha_client, &roses, &alice)?;
+    assert_eq!(roses_after, roses_before + 1);
+    // #endregion smart_contract_verify
+
+    // Finish the test successfully
+    Ok(())
+}
@@ -0,0 +1,92 @@
+//! Keeps a certificate in a `Store` asset: writes it, reads it back
+//! and removes some of its entries.
+
+use eyre::Result;
+use iroha_2_examples::{
+    ensure::ensure_asset_definition,
+    metadata,
+    store::{read_record, remove_entries, write_record, Entries, MissingEntry, StoreRecord},
+};
+use iroha_client::client::Client;
+use iroha_config::client::Configuration;
+use iroha_data_model::prelude::*;
+use std::{process::ExitCode, str::FromStr};
+
+/// A certificate, stored as one entry per field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Certificate {
+    /// Who passed the course.
+    pub holder: String,
+    /// The course.
+    pub course: String,
+    /// The grade, from 1 to 5.
+    pub grade: u32,
+    /// Whether the certificate is no longer valid.
+    pub revoked: bool,
+}
+
+impl StoreRecord for Certificate {
+    fn to_entries(&self) -> Result<Vec<(Name, Value)>> {
+        Ok(vec![
+            ("holder".parse()?, Value::String(self.holder.clone())),
+            ("course".parse()?, Value::String(self.course.clone())),
+            ("grade".parse()?, self.grade.to_value()),
+            ("revoked".parse()?, Value::Bool(self.revoked)),
+        ])
+    }
+
+    fn from_entries(entries: &Entries<'_>) -> Result<Self> {
+        Ok(Self {
+            holder: entries.required("holder")?,
+            course: entries.required("course")?,
+            grade: entries.required("grade")?,
+            // Certificates written before revocation existed have no such entry
+            revoked: entries.optional("revoked")?.unwrap_or(false),
+        })
+    }
+}
+
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run("Store asset", store_asset_test)
+}
+
+pub fn store_asset_test(config: &Configuration) -> Result<()> {
+    let client: Client = Client::new(config)?;
+
+    // A `Store` asset definition: its assets hold entries instead of an amount
+    let certificates = AssetDefinitionId::from_str("certificates#wonderland")?;
+    let ensured = ensure_asset_definition(&client, AssetDefinition::store(certificates.clone()))?;
+    println!("Asset definition `{certificates}` {ensured}");
+
+    // Alice writes a certificate into her own asset
+    let alice = AccountId::from_str("alice@wonderland")?;
+    let asset = AssetId::new(certificates, alice);
+    let certificate = Certificate {
+        holder: "Alice".to_owned(),
+        course: "Croquet with flamingos".to_owned(),
+        grade: 5,
+        revoked: false,
+    };
+    write_record(&client, &asset, &certificate)?;
+
+    // The whole certificate, read back from the asset
+    let read: Certificate = read_record(&client, &asset)?;
+    assert_eq!(read, certificate);
+    println!("Certificate from Iroha: {read:?}");
+
+    // A single entry, read with a query for the key
+    let grade: Option<u32> = metadata::get(&client, asset.clone(), &"grade".parse()?)?;
+    assert_eq!(grade, Some(5));
+
+    // Entries can be changed one by one
+    metadata::set(&client, asset.clone(), "revoked".parse()?, true)?;
+    assert!(read_record::<Certificate>(&client, &asset)?.revoked);
+
+    // Without the grade, the entries no longer make up a certificate
+    remove_entries(&client, &asset, ["grade".parse()?])?;
+    let error = read_record::<Certificate>(&client, &asset).expect_err("The grade is gone");
+    assert!(error.downcast_ref::<MissingEntry>().is_some());
+    println!("After removing the grade: {error}");
+
+    Ok(())
+}
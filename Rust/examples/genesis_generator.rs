@@ -0,0 +1,157 @@
+//! Generates a genesis block and writes it as JSON.
+//!
+//! ```bash
+//! cargo run --example genesis_generator -- \
+//!     --domains 1000 --accounts-per-domain 10 --assets-per-domain 2 \
+//!     --value-type quantity --initial-balance 100 \
+//!     --key-seed bench- --role auditor=CanSetParameters \
+//!     --validator ./validator.wasm --output ./genesis.json
+//! ```
+//!
+//! With `--key-seed`, the private key of `alice-0@wonderland-0` is derived
+//! from the seed `bench-alice-0@wonderland-0`, so the accounts can be used
+//! in benchmarks without storing their keys.
+
+use eyre::Result;
+use iroha_2_examples::{
+    genesis::{GenesisSpec, KeySource, RoleSpec, ValidatorSource},
+    genesis_writer::write_genesis,
+    harness::{Args, EXIT_CONFIG_FAILED},
+};
+use iroha_data_model::prelude::*;
+use std::{path::PathBuf, process::ExitCode};
+
+const USAGE: &str = "\
+Usage: genesis_generator [OPTIONS]
+
+Options:
+  --domains <N>               Number of domains [default: 1]
+  --accounts-per-domain <N>   Number of accounts in each domain [default: 1]
+  --assets-per-domain <N>     Number of asset definitions in each domain [default: 1]
+  --value-type <TYPE>         quantity, big_quantity, fixed or store [default: quantity]
+  --initial-balance <N>       Amount of each asset minted for each account [default: 0]
+  --public-key <KEY>          Give every account this multihash public key
+  --key-seed <PREFIX>         Derive the key of each account from `<PREFIX><account id>`
+  --random-keys               Give every account a random key [default]
+  --role <NAME>[=<TOKEN>,..]  Register a role with the given permission tokens and
+                              grant it to every account; may be repeated
+  --validator <PATH>          Path of the validator, relative to the genesis file
+                              [default: ./validator.wasm]
+  --inline-validator <PATH>   Build the validator crate at <PATH> and inline it
+  --output <PATH>             Where to write the genesis [default: ./genesis.json]
+  --help                      Print this message
+
+Values can also be given as `--option=<VALUE>`.";
+
+/// Parsed command line.
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// The genesis to generate.
+    pub spec: GenesisSpec,
+    /// Where to write it.
+    pub output: PathBuf,
+}
+
+fn parse_value_type(value: &str) -> Result<AssetValueType> {
+    Ok(match value {
+        "quantity" => AssetValueType::Quantity,
+        "big_quantity" => AssetValueType::BigQuantity,
+        "fixed" => AssetValueType::Fixed,
+        "store" => AssetValueType::Store,
+        _ => return Err(eyre::eyre!("Unknown value type `{value}`")),
+    })
+}
+
+fn parse_role(value: &str) -> Result<RoleSpec> {
+    let (name, tokens) = value.split_once('=').unwrap_or((value, ""));
+    let permissions = tokens
+        .split(',')
+        .filter(|token| !token.is_empty())
+        .map(|token| -> Result<_> {
+            Ok(PermissionToken::new(
+                token.parse()?,
+                &serde_json::Value::Null,
+            ))
+        })
+        .collect::<Result<_>>()?;
+    Ok(RoleSpec {
+        id: name.parse()?,
+        permissions,
+    })
+}
+
+impl Options {
+    /// Parse the arguments, without the program name.
+    ///
+    /// # Errors
+    /// Fails on unknown arguments and malformed values.
+    pub fn from_args(mut args: Args) -> Result<Self> {
+        let mut spec = GenesisSpec {
+            domains: 1,
+            accounts_per_domain: 1,
+            assets_per_domain: 1,
+            asset_value_type: AssetValueType::Quantity,
+            initial_balance: 0,
+            keys: KeySource::Random,
+            roles: Vec::new(),
+            validator: ValidatorSource::Path("./validator.wasm".into()),
+        };
+        let mut output = PathBuf::from("./genesis.json");
+
+        while let Some(flag) = args.next_flag()? {
+            match flag.as_str() {
+                "--domains" => spec.domains = args.parse()?,
+                "--accounts-per-domain" => spec.accounts_per_domain = args.parse()?,
+                "--assets-per-domain" => spec.assets_per_domain = args.parse()?,
+                "--value-type" => spec.asset_value_type = parse_value_type(&args.value()?)?,
+                "--initial-balance" => spec.initial_balance = args.parse()?,
+                "--public-key" => spec.keys = KeySource::Shared(args.parse()?),
+                "--key-seed" => spec.keys = KeySource::Seeded(args.value()?),
+                "--random-keys" => spec.keys = KeySource::Random,
+                "--role" => spec.roles.push(parse_role(&args.value()?)?),
+                "--validator" => spec.validator = ValidatorSource::Path(args.value()?.into()),
+                "--inline-validator" => {
+                    spec.validator = ValidatorSource::Inline(args.value()?.into());
+                }
+                "--output" => output = args.value()?.into(),
+                _ => return Err(eyre::eyre!("Unexpected argument `{flag}`\n\n{USAGE}")),
+            }
+        }
+        Ok(Self { spec, output })
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--help") {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    // The generator doesn't talk to a peer, so it doesn't need the harness
+    let options = match Options::from_args(Args::new(args)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("Genesis generation couldn't start: {error:?}");
+            return ExitCode::from(EXIT_CONFIG_FAILED);
+        }
+    };
+    match generate(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Genesis generation failed: {error:?}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+pub fn generate(options: &Options) -> Result<()> {
+    // The genesis is written domain by domain, so the memory use
+    // stays the same however many entities it has
+    let instructions = write_genesis(&options.spec, &options.output)?;
+    println!(
+        "Wrote {instructions} genesis instruction(s) to `{}`",
+        options.output.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,96 @@
+//! Compares a running network with the state described in a fixture file.
+//!
+//! ```bash
+//! cargo run --example ledger_drift -- --fixture fixtures/looking_glass.yaml \
+//!     --ignore-domain genesis --ignore-domain wonderland --format json
+//! ```
+//!
+//! The example fails if the ledger has drifted from the fixture, so it can
+//! be run as a check after a deployment.
+
+use eyre::Result;
+use iroha_2_examples::{
+    drift::{detect, Drift},
+    fixture::Fixture,
+    harness::Args,
+};
+use iroha_client::client::Client;
+use iroha_config::client::Configuration;
+use iroha_data_model::prelude::*;
+use std::{path::PathBuf, process::ExitCode};
+
+/// How the drift is printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// One line per difference.
+    Text,
+    /// A JSON document, see `iroha_2_examples::drift::Change`.
+    Json,
+}
+
+/// Command line options of the example.
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// The expected state.
+    pub fixture: PathBuf,
+    /// Domains that aren't reported as extra.
+    pub ignored: Vec<DomainId>,
+    /// How the drift is printed.
+    pub format: Format,
+}
+
+impl Options {
+    fn from_args(mut args: Args) -> Result<Self> {
+        let mut options = Self {
+            fixture: concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/looking_glass.yaml").into(),
+            ignored: Vec::new(),
+            format: Format::Text,
+        };
+        while let Some(flag) = args.next_flag()? {
+            match flag.as_str() {
+                "--fixture" => options.fixture = args.value()?.into(),
+                "--ignore-domain" => options.ignored.push(args.parse()?),
+                "--format" => {
+                    options.format = match args.value()?.as_str() {
+                        "text" => Format::Text,
+                        "json" => Format::Json,
+                        other => return Err(eyre::eyre!("Unknown format `{other}`")),
+                    }
+                }
+                _ => return Err(Args::unexpected(&flag)),
+            }
+        }
+        Ok(options)
+    }
+}
+
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run_with_args(
+        "Ledger drift",
+        Options::from_args,
+        |config, options| {
+            let drift = ledger_drift(config, options)?;
+            if drift.is_empty() {
+                Ok(())
+            } else {
+                Err(eyre::eyre!(
+                    "The ledger has drifted from `{}`",
+                    options.fixture.display()
+                ))
+            }
+        },
+    )
+}
+
+pub fn ledger_drift(config: &Configuration, options: &Options) -> Result<Drift> {
+    let client: Client = Client::new(config)?;
+    let fixture = Fixture::from_path(&options.fixture)?;
+
+    let drift = detect(&client, &fixture, &options.ignored)?;
+    match options.format {
+        Format::Text => println!("{drift}"),
+        Format::Json => println!("{}", drift.to_json()?),
+    }
+
+    Ok(drift)
+}
@@ -0,0 +1,90 @@
+//! Registers domains and accounts as fast as the peer takes them.
+//!
+//! ```bash
+//! cargo run --release --example load_generator -- --transactions 10000 --in-flight 128
+//! ```
+//!
+//! Each transaction registers a domain `load_{i}` and an account `bob@load_{i}`.
+
+use eyre::Result;
+use iroha_2_examples::{
+    harness::Args,
+    load::{LoadConfig, LoadGenerator, LoadReport},
+};
+use iroha_config::client::Configuration;
+use iroha_data_model::prelude::*;
+use std::{process::ExitCode, time::Duration};
+
+/// Command line options of the example.
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// Number of transactions to commit.
+    pub transactions: u32,
+    /// Index of the first domain, to run the generator again on the same peer.
+    pub first: u32,
+    /// Load generator parameters.
+    pub load: LoadConfig,
+}
+
+impl Options {
+    fn from_args(mut args: Args) -> Result<Self> {
+        let mut options = Self {
+            transactions: 1000,
+            first: 0,
+            load: LoadConfig::default(),
+        };
+        while let Some(flag) = args.next_flag()? {
+            match flag.as_str() {
+                "--transactions" => options.transactions = args.parse()?,
+                "--first" => options.first = args.parse()?,
+                "--in-flight" => options.load.max_in_flight = args.parse()?,
+                "--retries" => options.load.max_retries = args.parse()?,
+                "--refusals" => options.load.max_refusals = args.parse()?,
+                "--timeout-ms" => {
+                    options.load.status_timeout = Some(Duration::from_millis(args.parse()?));
+                }
+                _ => return Err(Args::unexpected(&flag)),
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// The instructions of the `i`-th transaction.
+pub fn register_domain_and_account(i: u32) -> Vec<InstructionBox> {
+    let domain_id: DomainId = format!("load_{i}").parse().expect("Valid");
+    let account_id = AccountId::new("bob".parse().expect("Valid"), domain_id.clone());
+    vec![
+        RegisterBox::new(Domain::new(domain_id)).into(),
+        RegisterBox::new(Account::new(account_id, [])).into(),
+    ]
+}
+
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run_with_args(
+        "Load generator",
+        Options::from_args,
+        |config, options| load_generator_test(config, options).map(|_| ()),
+    )
+}
+
+pub fn load_generator_test(config: &Configuration, options: &Options) -> Result<LoadReport> {
+    // The jobs are generated lazily, so any number of them can be submitted
+    let jobs =
+        (options.first..options.first + options.transactions).map(register_domain_and_account);
+    let report = LoadGenerator::new(config, options.load)?.run(jobs)?;
+    println!("{report}");
+    for (hash, rejection) in report.rejections.iter().take(5) {
+        println!("Transaction {hash} rejected: {rejection}");
+    }
+    for failure in report.failures.iter().take(5) {
+        println!("Submission refused: {failure}");
+    }
+
+    // Every job has reached a final status
+    assert_eq!(
+        report.committed + report.rejected + report.timed_out + report.failed,
+        u64::from(options.transactions)
+    );
+    Ok(report)
+}
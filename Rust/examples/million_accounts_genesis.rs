@@ -1,36 +1,34 @@
-use std::{thread, time::Duration};
-
-use iroha::samples::{construct_validator, get_config};
+use iroha::samples::get_config;
+use iroha_2_examples::{
+    genesis::{GenesisSpec, KeySource, ValidatorSource},
+    genesis_writer::write_genesis,
+    load::{LoadConfig, LoadGenerator},
+};
+use iroha_config::client::Configuration as ClientConfiguration;
 use iroha_data_model::prelude::*;
-use iroha_genesis::{GenesisNetwork, RawGenesisBlock, RawGenesisBlockBuilder};
+use iroha_genesis::{GenesisNetwork, RawGenesisBlock};
+use std::path::Path;
 use test_network::{
-    get_key_pair, wait_for_genesis_committed, Peer as TestPeer, PeerBuilder, TestRuntime,
+    get_key_pair, wait_for_genesis_committed, Peer as TestPeer, PeerBuilder,
+    TestClientConfiguration, TestRuntime,
 };
 use tokio::runtime::Runtime;
 
-fn generate_genesis(num_domains: u32) -> RawGenesisBlock {
-    let mut builder = RawGenesisBlockBuilder::new();
-
-    let key_pair = get_key_pair();
-    for i in 0_u32..num_domains {
-        builder = builder
-            .domain(format!("wonderland-{i}").parse().expect("Valid"))
-            .account(
-                format!("Alice-{i}").parse().expect("Valid"),
-                key_pair.public_key().clone(),
-            )
-            .asset(
-                format!("xor-{i}").parse().expect("Valid"),
-                AssetValueType::Quantity,
-            )
-            .finish_domain();
-    }
-
-    builder
-        .validator(
-            construct_validator("../default_validator").expect("Failed to construct validator"),
-        )
-        .build()
+// Stream the genesis to `path` domain by domain, then read it back.
+// Only the block read back is ever in memory, not the one being built.
+fn generate_genesis(num_domains: u32, path: &Path) -> RawGenesisBlock {
+    let spec = GenesisSpec {
+        domains: num_domains,
+        accounts_per_domain: 1,
+        assets_per_domain: 1,
+        asset_value_type: AssetValueType::Quantity,
+        initial_balance: 0,
+        keys: KeySource::Shared(get_key_pair().public_key().clone()),
+        roles: Vec::new(),
+        validator: ValidatorSource::Inline("../default_validator".into()),
+    };
+    write_genesis(&spec, path).expect("Failed to write genesis");
+    RawGenesisBlock::from_path(path).expect("Failed to read genesis")
 }
 
 fn main_genesis() {
@@ -40,8 +38,9 @@ fn main_genesis() {
         Some(get_key_pair()),
     );
     let rt = Runtime::test();
+    let path = std::env::temp_dir().join("million_accounts_genesis.json");
     let genesis = GenesisNetwork::from_configuration(
-        generate_genesis(1_000_000_u32),
+        generate_genesis(1_000_000_u32, &path),
         Some(&configuration.genesis),
     )
     .expect("genesis creation failed");
@@ -53,15 +52,16 @@ fn main_genesis() {
     // This only submits the genesis. It doesn't check if the accounts
     // are created, because that check is 1) not needed for what the
     // test is actually for, 2) incredibly slow, making this sort of
-    // test impractical, 3) very likely to overflow memory on systems
-    // with less than 16GiB of free memory.
+    // test impractical.
     rt.block_on(builder.start_with_peer(&mut peer));
+    std::fs::remove_file(path).expect("Failed to remove genesis");
 }
 
 fn create_million_accounts_directly() {
-    let (_rt, _peer, test_client) = <PeerBuilder>::new().start_with_runtime();
-    wait_for_genesis_committed(&vec![test_client.clone()], 0);
-    for i in 0_u32..1_000_000_u32 {
+    let (_rt, peer, test_client) = <PeerBuilder>::new().start_with_runtime();
+    wait_for_genesis_committed(&vec![test_client], 0);
+    let client_config = ClientConfiguration::test(&peer.api_address, &peer.telemetry_address);
+    let jobs = (0_u32..1_000_000_u32).map(|i| {
         let domain_id: DomainId = format!("wonderland-{i}").parse().expect("Valid");
         let normal_account_id = AccountId::new(
             format!("bob-{i}").parse().expect("Valid"),
@@ -69,14 +69,14 @@ fn create_million_accounts_directly() {
         );
         let create_domain = RegisterBox::new(Domain::new(domain_id));
         let create_account = RegisterBox::new(Account::new(normal_account_id.clone(), []));
-        if test_client
-            .submit_all([create_domain, create_account])
-            .is_err()
-        {
-            thread::sleep(Duration::from_millis(100));
-        }
-    }
-    thread::sleep(Duration::from_secs(1000));
+        vec![create_domain.into(), create_account.into()]
+    });
+    // Returns once every registration is committed or finally rejected
+    let report = LoadGenerator::new(&client_config, LoadConfig::default())
+        .expect("Failed to create the client")
+        .run(jobs)
+        .expect("Failed to run the registrations");
+    println!("{report}");
 }
 
 fn main() {
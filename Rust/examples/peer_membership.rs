@@ -0,0 +1,118 @@
+//! Rehearses a change of peers: starts a network in this process, registers
+//! a new peer, waits for it to catch up, then unregisters one of the
+//! original peers.
+//!
+//! ```bash
+//! cargo run --release --example peer_membership -- --peers 4 --timeout 60
+//! ```
+//!
+//! After each change, every peer still running must have committed the same
+//! number of blocks.
+
+use eyre::{Result, WrapErr};
+use iroha_2_examples::{
+    harness::{Args, EXIT_CONFIG_FAILED},
+    local_network::LocalNetwork,
+    query::PagedQuery,
+};
+use iroha_data_model::{prelude::*, query::peer::model::FindAllPeers};
+use std::{collections::BTreeSet, process::ExitCode, time::Duration};
+
+/// Command line options of the example.
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// Number of peers to start the network with.
+    pub peers: u32,
+    /// How long to wait for the peers to agree after each change.
+    pub timeout: Duration,
+}
+
+impl Options {
+    fn from_args(mut args: Args) -> Result<Self> {
+        let mut options = Self {
+            peers: 4,
+            timeout: Duration::from_secs(60),
+        };
+        while let Some(flag) = args.next_flag()? {
+            match flag.as_str() {
+                "--peers" => options.peers = args.parse()?,
+                "--timeout" => options.timeout = Duration::from_secs(args.parse()?),
+                _ => return Err(Args::unexpected(&flag)),
+            }
+        }
+        Ok(options)
+    }
+}
+
+fn main() -> ExitCode {
+    // The example starts its own peers, so it doesn't need the harness
+    let options = match Options::from_args(Args::from_env()) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("Peer membership example couldn't start: {error:?}");
+            return ExitCode::from(EXIT_CONFIG_FAILED);
+        }
+    };
+    let result = LocalNetwork::start(options.peers)
+        .and_then(|mut network| peer_membership(&mut network, &options));
+    match result {
+        Ok(height) => {
+            println!("Peer membership example works! The peers agree on {height} blocks");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("Peer membership example failed: {error:?}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Register a new peer on `network` and unregister one of its peers,
+/// and return the height the remaining peers agree on.
+pub fn peer_membership(network: &mut LocalNetwork, options: &Options) -> Result<u64> {
+    let height = network.wait_until_synced(1, options.timeout)?;
+    println!("{} peers at {height} blocks", network.peer_ids().len());
+
+    // The new peer runs but isn't part of the network yet
+    let joining = network.start_peer();
+    network
+        .client()
+        .submit_blocking(RegisterBox::new(Peer::new(joining.clone())))
+        .wrap_err(format!("Failed to register `{joining}`"))?;
+    assert!(registered_peers(network)?.contains(&joining));
+
+    // Once registered, it gets the blocks it missed from the others
+    let height = network.wait_until_synced(height + 1, options.timeout)?;
+    println!("`{joining}` joined at {height} blocks");
+
+    // Pick a peer other than the genesis one, which the client talks to
+    let leaving = network
+        .peer_ids()
+        .into_iter()
+        .skip(1)
+        .find(|id| id != &joining)
+        .ok_or_else(|| eyre::eyre!("There is no peer to unregister"))?;
+    network
+        .client()
+        .submit_blocking(UnregisterBox::new(IdBox::PeerId(leaving.clone())))
+        .wrap_err(format!("Failed to unregister `{leaving}`"))?;
+    assert!(!registered_peers(network)?.contains(&leaving));
+    network.stop_peer(&leaving)?;
+
+    // The remaining peers keep committing blocks without it
+    let domain: DomainId = "after_membership_change".parse()?;
+    network
+        .client()
+        .submit_blocking(RegisterBox::new(Domain::new(domain)))?;
+    let height = network.wait_until_synced(height + 2, options.timeout)?;
+    println!("`{leaving}` left, the others agree on {height} blocks");
+
+    Ok(height)
+}
+
+fn registered_peers(network: &LocalNetwork) -> Result<BTreeSet<PeerId>> {
+    PagedQuery::new(FindAllPeers)
+        .iter::<Peer>(network.client())
+        .map(|peer| peer.map(|peer| peer.id))
+        .collect()
+}
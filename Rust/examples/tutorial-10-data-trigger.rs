@@ -0,0 +1,74 @@
+use eyre::Result;
+use iroha_2_examples::{
+    ensure::ensure_asset_definition,
+    transfer::balance,
+    triggers::{account_created_in, register_data_trigger, trigger_exists, unregister_trigger},
+};
+use iroha_client::client::Client;
+use iroha_config::client::Configuration;
+use iroha_data_model::prelude::*;
+use std::{
+    process::ExitCode,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+// Register an account in `looking_glass` with a fresh key.
+// The names are new every run, so they don't clash with the accounts
+// of earlier runs or of the fixture, such as `march_hare`.
+fn register_account(client: &Client, name: &str) -> Result<()> {
+    let run = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+    let account_id = AccountId::new(format!("{name}_{run}").parse()?, "looking_glass".parse()?);
+    let (public_key, _) = iroha_crypto::KeyPair::generate()?.into();
+    let register: InstructionBox = RegisterBox::new(Account::new(account_id, [public_key])).into();
+    client.submit_blocking(register)?;
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run("Data trigger tutorial", data_trigger)
+}
+
+pub fn data_trigger(config: &Configuration) -> Result<()> {
+    let client: Client = Client::new(config)?;
+
+    // Alice counts the accounts registered in `looking_glass`,
+    // carrying on from the count of earlier runs
+    let alice = AccountId::from_str("alice@wonderland")?;
+    let counter = AssetDefinitionId::from_str("registered_accounts#wonderland")?;
+    let ensured = ensure_asset_definition(&client, AssetDefinition::quantity(counter.clone()))?;
+    println!("Asset definition `{counter}` {ensured}");
+    let counted_before = balance::<u32>(&client, &counter, &alice)?;
+
+    // The trigger increments the counter on every account registration there
+    let trigger_id = TriggerId::from_str("count_accounts")?;
+    let increment: InstructionBox = MintBox::new(
+        1_u32.to_value(),
+        IdBox::AssetId(AssetId::new(counter.clone(), alice.clone())),
+    )
+    .into();
+    register_data_trigger(
+        &client,
+        trigger_id.clone(),
+        [increment],
+        account_created_in("looking_glass".parse()?),
+    )?;
+    assert!(trigger_exists(&client, &trigger_id)?);
+
+    // The trigger runs when the registration is committed
+    register_account(&client, "dormouse")?;
+    register_account(&client, "march_hare")?;
+    let counted = balance::<u32>(&client, &counter, &alice)?;
+    println!("Accounts counted by the trigger: {counted}");
+    assert_eq!(counted, counted_before + 2);
+
+    // Once unregistered, the trigger counts no more
+    unregister_trigger(&client, &trigger_id)?;
+    assert!(!trigger_exists(&client, &trigger_id)?);
+    let counted = balance::<u32>(&client, &counter, &alice)?;
+    register_account(&client, "caterpillar")?;
+    register_account(&client, "duchess")?;
+    assert_eq!(balance::<u32>(&client, &counter, &alice)?, counted);
+
+    Ok(())
+}
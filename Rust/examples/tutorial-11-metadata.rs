@@ -0,0 +1,116 @@
+use eyre::Result;
+use iroha_2_examples::{
+    identities::Identities,
+    keys,
+    metadata::{self, WrongType},
+    rejection::Rejection,
+    status::submit_instructions,
+};
+use iroha_client::client::Client;
+use iroha_config::client::Configuration;
+use iroha_data_model::{metadata::MetadataError, prelude::*};
+use std::{process::ExitCode, str::FromStr};
+
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run("Metadata tutorial", metadata_tutorial)
+}
+
+pub fn metadata_tutorial(config: &Configuration) -> Result<()> {
+    let alice_client: Client = Client::new(config)?;
+
+    // Alice registered the domain and the time asset,
+    // so she can attach metadata to them
+    let looking_glass = DomainId::from_str("looking_glass")?;
+    let motto = Name::from_str("motto")?;
+    metadata::set(
+        &alice_client,
+        looking_glass.clone(),
+        motto.clone(),
+        "Curiouser and curiouser".to_owned(),
+    )?;
+    let read: Option<String> = metadata::get(&alice_client, looking_glass.clone(), &motto)?;
+    assert_eq!(read.as_deref(), Some("Curiouser and curiouser"));
+    println!("The motto of `{looking_glass}`: {read:?}");
+
+    // Reading the entry as another type fails
+    let error = metadata::get::<u32>(&alice_client, looking_glass, &motto)
+        .expect_err("The motto is a string");
+    assert!(error.downcast_ref::<WrongType>().is_some());
+    println!("Reading the motto as a number: {error}");
+
+    // Numbers can be updated from their current value
+    let time = AssetDefinitionId::from_str("time#looking_glass")?;
+    let clocks = Name::from_str("clocks")?;
+    let count = metadata::update(&alice_client, time.clone(), clocks.clone(), |count| {
+        count.unwrap_or(0_u32) + 1
+    })?;
+    let read: Option<u32> = metadata::get(&alice_client, time, &clocks)?;
+    assert_eq!(read, Some(count));
+    println!("Clocks showing the time: {count}");
+
+    // Only the white rabbit changes his own profile, so he signs these.
+    // His key pair is derived from a public seed, as in the account
+    // registration tutorial, which is only fine for a tutorial.
+    let mut identities = Identities::new(config)?;
+    let white_rabbit = AccountId::from_str("white_rabbit@looking_glass")?;
+    identities.add(
+        white_rabbit.clone(),
+        keys::from_seed(keys::WHITE_RABBIT_SEED)?,
+    );
+    let rabbit_client = identities.client(&white_rabbit)?;
+
+    let nickname = Name::from_str("nickname")?;
+    let minutes_late = Name::from_str("minutes_late")?;
+    metadata::set(
+        &rabbit_client,
+        white_rabbit.clone(),
+        nickname.clone(),
+        "Rabbit".to_owned(),
+    )?;
+    metadata::set(
+        &rabbit_client,
+        white_rabbit.clone(),
+        minutes_late.clone(),
+        5_u32,
+    )?;
+    // Setting an existing key replaces the value
+    metadata::set(
+        &rabbit_client,
+        white_rabbit.clone(),
+        minutes_late.clone(),
+        10_u32,
+    )?;
+    let late: Option<u32> = metadata::get(&rabbit_client, white_rabbit.clone(), &minutes_late)?;
+    assert_eq!(late, Some(10));
+    println!(
+        "The white rabbit is {} minutes late",
+        late.unwrap_or_default()
+    );
+
+    // Removed entries are gone
+    metadata::remove(&rabbit_client, white_rabbit.clone(), nickname.clone())?;
+    let removed: Option<String> = metadata::get(&rabbit_client, white_rabbit.clone(), &nickname)?;
+    assert_eq!(removed, None);
+
+    // Entries are limited in size by the peer, 4096 bytes by default.
+    // Follow the transaction to see why it is rejected.
+    let diary = Name::from_str("diary")?;
+    let write_diary: InstructionBox = SetKeyValueBox::new(
+        IdBox::AccountId(white_rabbit.clone()),
+        diary,
+        Value::String("Late! ".repeat(1000)),
+    )
+    .into();
+    let rabbit_config = identities.config(&white_rabbit)?;
+    let outcome = submit_instructions(&rabbit_client, &rabbit_config, [write_diary])?.wait()?;
+    let rejection = outcome
+        .rejection()
+        .ok_or_else(|| eyre::eyre!("The long diary wasn't rejected: {outcome}"))?;
+    println!("Writing a long diary: {rejection}");
+    assert!(matches!(
+        rejection,
+        Rejection::Metadata(MetadataError::EntryTooBig(_))
+    ));
+
+    Ok(())
+}
@@ -1,27 +1,37 @@
 use eyre::Result;
+use iroha_2_examples::{ensure::ensure_domain, query::PagedQuery};
 use iroha_client::client::Client;
+use iroha_config::client::Configuration;
 use iroha_data_model::prelude::*;
-
-use iroha_2_examples::load_client;
+use std::process::ExitCode;
 
 // TODO: move to prelude?
 use iroha_data_model::query::domain::model::FindAllDomains;
 
-fn main() -> Result<()> {
-    let client: Client = load_client("./config.json")?;
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run("Domain registration tutorial", register_domain)
+}
+
+pub fn register_domain(config: &Configuration) -> Result<()> {
+    let client: Client = Client::new(config)?;
 
     // Create a Domain Id
     let looking_glass: DomainId = "looking_glass".parse()?;
 
-    // Register the domain
-    let register: InstructionBox = RegisterBox::new(Domain::new(looking_glass.clone())).into();
-    client.submit_blocking(register)?;
+    // Register the domain, unless it's there from an earlier run
+    let ensured = ensure_domain(&client, Domain::new(looking_glass.clone()))?;
+    println!("Domain `{looking_glass}` {ensured}");
 
-    // Check what domains there are now
-    let domains = client
-        .request(FindAllDomains)?
-        .collect::<Result<Vec<_>, _>>()?;
-    assert!(domains.iter().find(|x| x.id == looking_glass).is_some());
+    // Check what domains there are now.
+    // The domains are fetched page by page, stopping at the first match.
+    let mut found = false;
+    for domain in PagedQuery::new(FindAllDomains).iter::<Domain>(&client) {
+        if domain?.id == looking_glass {
+            found = true;
+            break;
+        }
+    }
+    assert!(found);
 
     Ok(())
 }
@@ -1,19 +1,27 @@
 use eyre::Result;
-use iroha_2_examples::load_client;
+use iroha_2_examples::{ensure::ensure_account, keys, query::PagedQuery};
 use iroha_client::client::Client;
+use iroha_config::client::Configuration;
 use iroha_data_model::prelude::*;
 use iroha_data_model::query::account::model::FindAllAccounts;
+use std::process::ExitCode;
 
 // This function imitates the user sharing a public key.
 // It generates a new public key for a new account.
-// Normally, it is generated by Kagami.
+// Normally, it is generated by Kagami. The key is derived from a seed,
+// like `kagami crypto --seed`, so the later tutorials can sign as the white rabbit.
+// The seed is public, so never do this for a real account.
 fn get_key_from_white_rabbit() -> Result<PublicKey> {
-    let (public_key, _) = iroha_crypto::KeyPair::generate()?.into();
+    let (public_key, _) = keys::from_seed(keys::WHITE_RABBIT_SEED)?.into();
     Ok(public_key)
 }
 
-fn main() -> Result<()> {
-    let client: Client = load_client("./config.json")?;
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run("Account registration tutorial", register_account)
+}
+
+pub fn register_account(config: &Configuration) -> Result<()> {
+    let client: Client = Client::new(config)?;
 
     // Create an AccountId instance by providing the account and domain name
     let account_id: AccountId = "white_rabbit@looking_glass".parse().expect(
@@ -27,14 +35,20 @@ fn main() -> Result<()> {
     // Use a public key that was shared by the white_rabbit.
     let public_key = get_key_from_white_rabbit()?;
 
-    // Generate a new account
-    let register = RegisterBox::new(Account::new(account_id.clone(), [public_key]));
-    client.submit_blocking(register)?;
+    // Generate a new account and register it, unless it's there from an earlier run
+    let ensured = ensure_account(&client, Account::new(account_id.clone(), [public_key]))?;
+    println!("Account `{account_id}` {ensured}");
 
-    let accounts = client
-        .request(FindAllAccounts)?
-        .collect::<Result<Vec<_>, _>>()?;
-    assert!(accounts.iter().find(|x| x.id == account_id).is_some());
+    // Check that the account is there.
+    // The accounts are fetched page by page, stopping at the first match.
+    let mut found = false;
+    for account in PagedQuery::new(FindAllAccounts).iter::<Account>(&client) {
+        if account?.id == account_id {
+            found = true;
+            break;
+        }
+    }
+    assert!(found);
 
     Ok(())
 }
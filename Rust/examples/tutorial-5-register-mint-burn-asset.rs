@@ -1,19 +1,28 @@
 use eyre::Result;
-use iroha_2_examples::load_client;
+use iroha_2_examples::ensure::ensure_asset_definition;
 use iroha_client::client::Client;
+use iroha_config::client::Configuration;
 use iroha_data_model::prelude::*;
-use std::str::FromStr;
+use std::{process::ExitCode, str::FromStr};
 
-fn main() -> Result<()> {
-    let client: Client = load_client("./config.json")?;
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run("Asset tutorial", register_mint_burn_asset)
+}
+
+pub fn register_mint_burn_asset(config: &Configuration) -> Result<()> {
+    let client: Client = Client::new(config)?;
 
     // Create an Asset Definition Id of the time
     let time_definition = AssetDefinitionId::from_str("time#looking_glass")?;
 
-    // Register the time asset
-    let register_time: InstructionBox =
-        RegisterBox::new(AssetDefinition::fixed(time_definition.clone()).mintable_once()).into();
-    client.submit_blocking(register_time)?;
+    // Register the time asset, unless it's there from an earlier run.
+    // It is mintable once: after the first mint, its supply is fixed
+    // (see the `client_mintability` example).
+    let ensured = ensure_asset_definition(
+        &client,
+        AssetDefinition::fixed(time_definition.clone()).mintable_once(),
+    )?;
+    println!("Asset definition `{time_definition}` {ensured}");
 
     // Check if the asset exists
     {
@@ -60,6 +69,7 @@ fn main() -> Result<()> {
 
 // Output of this example:
 //
+// Asset definition `time#looking_glass` registered
 // Time asset definition from Iroha: AssetDefinition { id: time#looking_glass, value_type: Fixed, mintable: Once, logo: None, metadata: Metadata { map: {} }, owned_by: alice@wonderland }
 // Asset data after mint: Asset { id: time##white_rabbit@looking_glass, value: Fixed(Fixed(12.34)) }
 // Asset data after burn: Asset { id: time##white_rabbit@looking_glass, value: Fixed(Fixed(10.34)) }
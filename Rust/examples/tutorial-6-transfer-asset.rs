@@ -0,0 +1,100 @@
+use eyre::Result;
+use iroha_2_examples::{
+    ensure::ensure_asset_definition,
+    transfer::{transfer, InsufficientFunds},
+};
+use iroha_client::client::Client;
+use iroha_config::client::Configuration;
+use iroha_data_model::prelude::*;
+use std::{process::ExitCode, str::FromStr};
+
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run("Asset transfer tutorial", transfer_assets)
+}
+
+pub fn transfer_assets(config: &Configuration) -> Result<()> {
+    let client: Client = Client::new(config)?;
+
+    // Alice signs the transactions, so she is the sender.
+    // The receiver was registered in the account registration tutorial.
+    let alice = AccountId::from_str("alice@wonderland")?;
+    let white_rabbit = AccountId::from_str("white_rabbit@looking_glass")?;
+
+    // `Quantity` assets are transferred as `u32`.
+    // Alice got some roses in the genesis block.
+    let rose = AssetDefinitionId::from_str("rose#wonderland")?;
+    let report = transfer(&client, &rose, &alice, &white_rabbit, 3_u32)?;
+    assert_eq!(report.sender_after, report.sender_before - 3);
+    assert_eq!(report.receiver_after, report.receiver_before + 3);
+    println!("Roses: {report:?}");
+
+    // `BigQuantity` assets are transferred as `u128`.
+    // The definitions are registered unless an earlier run registered them.
+    let tea = AssetDefinitionId::from_str("tea#wonderland")?;
+    let ensured = ensure_asset_definition(&client, AssetDefinition::big_quantity(tea.clone()))?;
+    println!("Asset definition `{tea}` {ensured}");
+    let mint_tea = MintBox::new(
+        10_000_000_000_000_u128.to_value(),
+        IdBox::AssetId(AssetId::new(tea.clone(), alice.clone())),
+    );
+    client.submit_blocking(mint_tea)?;
+
+    let report = transfer(&client, &tea, &alice, &white_rabbit, 4_000_000_000_000_u128)?;
+    assert_eq!(
+        report.sender_after,
+        report.sender_before - 4_000_000_000_000
+    );
+    assert_eq!(
+        report.receiver_after,
+        report.receiver_before + 4_000_000_000_000
+    );
+    println!("Tea: {report:?}");
+
+    // `Fixed` assets are transferred as `Fixed`
+    let cake = AssetDefinitionId::from_str("cake#wonderland")?;
+    let ensured = ensure_asset_definition(&client, AssetDefinition::fixed(cake.clone()))?;
+    println!("Asset definition `{cake}` {ensured}");
+    let mint_cake = MintBox::new(
+        5.5_f64.try_to_value()?,
+        IdBox::AssetId(AssetId::new(cake.clone(), alice.clone())),
+    );
+    client.submit_blocking(mint_cake)?;
+
+    let slice = Fixed::try_from(1.25_f64)?;
+    let report = transfer(&client, &cake, &alice, &white_rabbit, slice)?;
+    assert_eq!(
+        report.sender_after,
+        report.sender_before.checked_sub(slice)?
+    );
+    assert_eq!(
+        report.receiver_after,
+        report.receiver_before.checked_add(slice)?
+    );
+    println!("Cake: {report:?}");
+
+    // `Store` assets hold key-value pairs instead of an amount, so they are
+    // out of scope here: the peer only transfers numeric assets, and
+    // `transfer` doesn't accept them. Their entries are written and read
+    // as shown in the `client_store_asset` example.
+
+    // Transferring more than the sender holds is caught before submitting...
+    let error = transfer(&client, &rose, &alice, &white_rabbit, 1_000_u32)
+        .expect_err("Alice doesn't have that many roses");
+    let insufficient = error
+        .downcast_ref::<InsufficientFunds<u32>>()
+        .expect("The balance is checked before submitting");
+    println!("Transfer refused: {insufficient}");
+
+    // ...and the peer rejects such a transaction anyway
+    let overdraft = TransferBox::new(
+        IdBox::AssetId(AssetId::new(rose, alice)),
+        1_000_u32.to_value(),
+        IdBox::AccountId(white_rabbit),
+    );
+    let error = client
+        .submit_blocking(overdraft)
+        .expect_err("The peer doesn't allow negative balances");
+    println!("Transfer rejected: {error:?}");
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use eyre::Result;
+use iroha_2_examples::{
+    ensure::ensure_role,
+    keys,
+    rejection::Rejection,
+    status::{submit_instructions, Outcome},
+    transfer::balance,
+};
+use iroha_client::client::Client;
+use iroha_config::client::Configuration;
+use iroha_data_model::prelude::*;
+use iroha_data_model::query::role::model::FindRolesByAccountId;
+use serde_json::json;
+use std::{process::ExitCode, str::FromStr};
+
+// The roles the account has right now.
+fn roles_of(client: &Client, account_id: &AccountId) -> Result<Vec<RoleId>> {
+    Ok(client
+        .request(FindRolesByAccountId {
+            id: account_id.clone().into(),
+        })?
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run("Roles and permissions tutorial", roles_and_permissions)
+}
+
+pub fn roles_and_permissions(config: &Configuration) -> Result<()> {
+    // Alice owns the roses and manages the role
+    let alice_client: Client = Client::new(config)?;
+    let alice = AccountId::from_str("alice@wonderland")?;
+    let alice_roses = AssetId::new(AssetDefinitionId::from_str("rose#wonderland")?, alice);
+
+    // The white rabbit signs his own transactions. His key pair is derived
+    // from a public seed, as in the account registration tutorial, which is
+    // only fine for a tutorial: anyone can derive the private key.
+    let white_rabbit = AccountId::from_str("white_rabbit@looking_glass")?;
+    let white_rabbit_key_pair = keys::from_seed(keys::WHITE_RABBIT_SEED)?;
+    let white_rabbit_config = keys::signing_as(config, white_rabbit.clone(), white_rabbit_key_pair);
+    let white_rabbit_client = Client::new(&white_rabbit_config)?;
+
+    // The action under test: the white rabbit takes a rose from Alice
+    let take_rose = || -> Result<Outcome> {
+        let transfer = TransferBox::new(
+            IdBox::AssetId(alice_roses.clone()),
+            1_u32.to_value(),
+            IdBox::AccountId(white_rabbit.clone()),
+        );
+        submit_instructions(
+            &white_rabbit_client,
+            &white_rabbit_config,
+            [transfer.into()],
+        )?
+        .wait()
+    };
+    // Any other rejection, e.g. of a wrong signature, would be a mistake
+    let take_rose_not_permitted = || -> Result<Rejection> {
+        let outcome = take_rose()?;
+        match outcome.rejection() {
+            Some(rejection @ Rejection::NotPermitted(_)) => Ok(rejection),
+            _ => Err(eyre::eyre!("Taking a rose was {outcome}, not refused")),
+        }
+    };
+    let rabbit_roses = || balance::<u32>(&alice_client, &alice_roses.definition_id, &white_rabbit);
+
+    // Nobody can move Alice's roses but Alice
+    let before = rabbit_roses()?;
+    let rejection = take_rose_not_permitted()?;
+    println!("Before the grant: {rejection}");
+    assert_eq!(rabbit_roses()?, before);
+
+    // A role is a named set of permission tokens.
+    // This one allows transferring Alice's roses, and nothing else.
+    // It is registered unless an earlier run registered it.
+    let role_id = RoleId::from_str("rose_courier")?;
+    let can_transfer_roses = PermissionToken::new(
+        "CanTransferUserAsset".parse()?,
+        &json!({ "asset_id": alice_roses }),
+    );
+    let ensured = ensure_role(&alice_client, role_id.clone(), [can_transfer_roses])?;
+    println!("Role `{role_id}` {ensured}");
+
+    // Grant the role to the white rabbit
+    let grant: InstructionBox = GrantBox::new(role_id.clone(), white_rabbit.clone()).into();
+    alice_client.submit_blocking(grant)?;
+    assert!(roles_of(&alice_client, &white_rabbit)?.contains(&role_id));
+
+    // Now the same transaction goes through
+    let outcome = take_rose()?;
+    if !matches!(outcome, Outcome::Committed) {
+        return Err(eyre::eyre!("Taking a rose after the grant was {outcome}"));
+    }
+    assert_eq!(rabbit_roses()?, before + 1);
+    println!(
+        "After the grant: the white rabbit has {} rose(s)",
+        before + 1
+    );
+
+    // Revoke the role, and the permission goes with it
+    let revoke: InstructionBox = RevokeBox::new(role_id.clone(), white_rabbit.clone()).into();
+    alice_client.submit_blocking(revoke)?;
+    assert!(!roles_of(&alice_client, &white_rabbit)?.contains(&role_id));
+
+    let rejection = take_rose_not_permitted()?;
+    println!("After the revoke: {rejection}");
+    assert_eq!(rabbit_roses()?, before + 1);
+
+    Ok(())
+}
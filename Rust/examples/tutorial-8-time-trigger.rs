@@ -0,0 +1,111 @@
+use eyre::Result;
+use iroha_2_examples::{
+    transfer::balance,
+    triggers::{register_time_trigger, trigger_exists, unregister_trigger},
+};
+use iroha_client::client::Client;
+use iroha_config::client::Configuration;
+use iroha_data_model::prelude::*;
+use std::{
+    process::ExitCode,
+    str::FromStr,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+// Time triggers are checked when a block is committed,
+// so something has to make blocks while we wait.
+// Setting a metadata entry of Alice is enough to commit one.
+fn tick_block(client: &Client, tick: u32) -> Result<()> {
+    let alice = AccountId::from_str("alice@wonderland")?;
+    let set_tick: InstructionBox =
+        SetKeyValueBox::new(alice, Name::from_str("tick")?, tick.to_value()).into();
+    client.submit_blocking(set_tick)?;
+    Ok(())
+}
+
+// The current time, measured from the Unix epoch like the block times.
+fn now() -> Result<Duration> {
+    Ok(SystemTime::now().duration_since(UNIX_EPOCH)?)
+}
+
+// Wait until `at`, measured from the Unix epoch.
+fn sleep_until(at: Duration) -> Result<()> {
+    thread::sleep(at.saturating_sub(now()?));
+    Ok(())
+}
+
+// How many of the times scheduled from `start` every `period`
+// come after `after` and no later than `until`.
+fn scheduled_between(start: Duration, period: Duration, after: Duration, until: Duration) -> u32 {
+    let count = (0..)
+        .map(|k| start + period * k)
+        .skip_while(|at| *at <= after)
+        .take_while(|at| *at <= until)
+        .count();
+    u32::try_from(count).expect("A handful of periods")
+}
+
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run("Time trigger tutorial", time_trigger)
+}
+
+pub fn time_trigger(config: &Configuration) -> Result<()> {
+    let client: Client = Client::new(config)?;
+
+    let alice = AccountId::from_str("alice@wonderland")?;
+    let rose = AssetDefinitionId::from_str("rose#wonderland")?;
+    let alice_roses = AssetId::new(rose.clone(), alice.clone());
+
+    // Mint a rose for Alice every few seconds, starting a little later,
+    // so the first scheduled time comes after the trigger is registered
+    let trigger_id = TriggerId::from_str("rose_every_period")?;
+    let mint_rose: InstructionBox =
+        MintBox::new(1_u32.to_value(), IdBox::AssetId(alice_roses)).into();
+    let period = Duration::from_secs(4);
+    let start = now()? + period / 2;
+
+    let roses_before = balance::<u32>(&client, &rose, &alice)?;
+    register_time_trigger(&client, trigger_id.clone(), [mint_rose], start, period)?;
+    // The block registering the trigger is committed, so it was made earlier
+    let registered_at = now()?;
+    assert!(trigger_exists(&client, &trigger_id)?);
+
+    // Commit a block in the middle of each period. Each block then covers
+    // exactly one scheduled time, so the trigger runs once per tick.
+    let ticks = 3;
+    let mut last_tick_sent = Duration::ZERO;
+    for tick in 0..ticks {
+        sleep_until(start + period * tick + period / 2)?;
+        // The block with the tick is made after it is sent
+        last_tick_sent = now()?;
+        tick_block(&client, tick)?;
+    }
+    let last_tick_committed = now()?;
+    let roses_after = balance::<u32>(&client, &rose, &alice)?;
+    println!("Roses: {roses_before} before the trigger, {roses_after} after {ticks} periods");
+
+    // A slow peer may commit the trigger after its first scheduled time,
+    // which is then missed, or the last tick after the next scheduled time,
+    // which is then covered too. Whatever the peer's pace, the times
+    // between the registration and the last tick are covered, and no time
+    // after the last tick is.
+    let least = scheduled_between(start, period, registered_at, last_tick_sent);
+    let most = scheduled_between(start, period, Duration::ZERO, last_tick_committed);
+    let runs = roses_after - roses_before;
+    assert!(
+        (least..=most).contains(&runs),
+        "The trigger ran {runs} times, expected {least} to {most}"
+    );
+
+    // Once unregistered, the trigger mints no more
+    unregister_trigger(&client, &trigger_id)?;
+    assert!(!trigger_exists(&client, &trigger_id)?);
+
+    let roses_unregistered = balance::<u32>(&client, &rose, &alice)?;
+    sleep_until(start + period * ticks + period / 2)?;
+    tick_block(&client, ticks)?;
+    assert_eq!(balance::<u32>(&client, &rose, &alice)?, roses_unregistered);
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use eyre::Result;
+use iroha_2_examples::{
+    transfer::balance,
+    triggers::{call_trigger, register_by_call_trigger, trigger_exists, unregister_trigger},
+};
+use iroha_client::client::Client;
+use iroha_config::client::Configuration;
+use iroha_data_model::prelude::*;
+use std::{process::ExitCode, str::FromStr};
+
+fn main() -> ExitCode {
+    iroha_2_examples::harness::run("By-call trigger tutorial", by_call_trigger)
+}
+
+pub fn by_call_trigger(config: &Configuration) -> Result<()> {
+    let client: Client = Client::new(config)?;
+
+    let alice = AccountId::from_str("alice@wonderland")?;
+    let rose = AssetDefinitionId::from_str("rose#wonderland")?;
+    let alice_roses = AssetId::new(rose.clone(), alice.clone());
+
+    // The trigger mints five roses for Alice, but only when it is called
+    let trigger_id = TriggerId::from_str("five_roses")?;
+    let mint_roses: InstructionBox =
+        MintBox::new(5_u32.to_value(), IdBox::AssetId(alice_roses)).into();
+    register_by_call_trigger(&client, trigger_id.clone(), [mint_roses])?;
+    assert!(trigger_exists(&client, &trigger_id)?);
+
+    // Registering it doesn't run it
+    let roses_before = balance::<u32>(&client, &rose, &alice)?;
+
+    // Each call is an `ExecuteTriggerBox` instruction
+    call_trigger(&client, &trigger_id)?;
+    call_trigger(&client, &trigger_id)?;
+    let roses_after = balance::<u32>(&client, &rose, &alice)?;
+    println!("Roses: {roses_before} before the calls, {roses_after} after two calls");
+    assert_eq!(roses_after, roses_before + 10);
+
+    // Once unregistered, the trigger can't be called anymore
+    unregister_trigger(&client, &trigger_id)?;
+    assert!(!trigger_exists(&client, &trigger_id)?);
+    let error = call_trigger(&client, &trigger_id).expect_err("The trigger is gone");
+    println!("Calling an unregistered trigger: {error:?}");
+    assert_eq!(balance::<u32>(&client, &rose, &alice)?, roses_after);
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+//! Smart contract minting a rose for the account that submits it.
+
+#![no_std]
+
+extern crate alloc;
+#[cfg(not(test))]
+extern crate panic_halt;
+
+use core::str::FromStr as _;
+
+use iroha_wasm::{data_model::prelude::*, debug::DebugUnwrapExt, ExecuteOnHost as _};
+use lol_alloc::{FreeListAllocator, LockedAllocator};
+
+#[global_allocator]
+static ALLOC: LockedAllocator<FreeListAllocator> = LockedAllocator::new(FreeListAllocator::new());
+
+/// Mint 1 rose for `authority`, the account that submitted the transaction.
+#[iroha_wasm::main(params = "[authority]")]
+fn main(authority: AccountId) {
+    let rose_definition_id = AssetDefinitionId::from_str("rose#wonderland").dbg_unwrap();
+    let rose_id = AssetId::new(rose_definition_id, authority);
+
+    MintBox::new(1_u32.to_value(), IdBox::AssetId(rose_id))
+        .execute()
+        .dbg_expect("Failed to mint a rose");
+}
@@ -0,0 +1,205 @@
+//! Local append-only archive of committed blocks.
+//!
+//! The archive is a file with one JSON record per line. Each record holds the
+//! block together with its height and hash, so an archive can be resumed
+//! without decoding the blocks, and each appended block is checked to extend
+//! the chain of the previous one.
+
+use std::{
+    fmt,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Seek, SeekFrom, Write},
+    num::NonZeroU64,
+    path::{Path, PathBuf},
+};
+
+use eyre::{Result, WrapErr};
+use iroha_client::client::Client;
+use iroha_crypto::Hash;
+use iroha_data_model::block::VersionedCommittedBlock;
+use serde::{Deserialize, Serialize};
+
+/// The last block in the archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tip {
+    /// Height of the block.
+    pub height: u64,
+    /// Hash of the block.
+    pub hash: Hash,
+}
+
+/// A block that doesn't extend the chain of the archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkageError {
+    /// The last block in the archive.
+    pub tip: Tip,
+    /// Height of the offending block.
+    pub height: u64,
+    /// Hash of the block preceding the offending one, as declared by it.
+    pub previous_block_hash: Option<Hash>,
+}
+
+impl fmt::Display for LinkageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "block {} (previous {:?}) doesn't follow block {} ({})",
+            self.height, self.previous_block_hash, self.tip.height, self.tip.hash
+        )
+    }
+}
+
+impl std::error::Error for LinkageError {}
+
+/// A record of the archive.
+#[derive(Serialize)]
+struct Record<'block> {
+    height: u64,
+    hash: Hash,
+    previous_block_hash: Option<Hash>,
+    block: &'block VersionedCommittedBlock,
+}
+
+/// The part of a record needed to resume the archive.
+#[derive(Deserialize)]
+struct RecordHeader {
+    height: u64,
+    hash: Hash,
+}
+
+/// An archive file opened for appending.
+#[derive(Debug)]
+pub struct BlockArchive {
+    path: PathBuf,
+    file: File,
+    tip: Option<Tip>,
+}
+
+impl BlockArchive {
+    /// Open the archive at `path`, creating it if it doesn't exist.
+    ///
+    /// A partially written last record, left by an interrupted run,
+    /// is dropped.
+    ///
+    /// # Errors
+    /// Fails if the file can't be opened or holds a malformed record.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&path)
+            .wrap_err(format!("Failed to open `{}`", path.display()))?;
+
+        let mut tip = None;
+        let mut complete_len = 0_u64;
+        let mut reader = BufReader::new(&mut file);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = reader.read_line(&mut line)?;
+            if read == 0 || !line.ends_with('\n') {
+                break;
+            }
+            let header: RecordHeader = serde_json::from_str(&line).wrap_err(format!(
+                "Malformed record after byte {complete_len} of `{}`",
+                path.display()
+            ))?;
+            tip = Some(Tip {
+                height: header.height,
+                hash: header.hash,
+            });
+            complete_len += read as u64;
+        }
+        file.set_len(complete_len)?;
+        file.seek(SeekFrom::End(0))?;
+
+        Ok(Self { path, file, tip })
+    }
+
+    /// Path of the archive file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The last archived block, if any.
+    pub fn tip(&self) -> Option<Tip> {
+        self.tip
+    }
+
+    /// Height of the block to archive next.
+    pub fn next_height(&self) -> NonZeroU64 {
+        let height = self.tip.map_or(1, |tip| tip.height + 1);
+        NonZeroU64::new(height).expect("Heights start at 1")
+    }
+
+    /// Append `block`, checking that it follows the last archived one.
+    ///
+    /// # Errors
+    /// Fails with [`LinkageError`] if the block doesn't extend the chain,
+    /// and otherwise if it can't be written.
+    pub fn append(&mut self, block: &VersionedCommittedBlock) -> Result<Tip> {
+        let header = block.header();
+        let previous_block_hash = header.previous_block_hash.map(Hash::from);
+        if let Some(tip) = self.tip {
+            if header.height != tip.height + 1 || previous_block_hash != Some(tip.hash) {
+                return Err(LinkageError {
+                    tip,
+                    height: header.height,
+                    previous_block_hash,
+                }
+                .into());
+            }
+        }
+
+        let record = Record {
+            height: header.height,
+            hash: block.hash().into(),
+            previous_block_hash,
+            block,
+        };
+        let mut line = serde_json::to_vec(&record)?;
+        line.push(b'\n');
+        // The record is written at once, so an interrupted run leaves
+        // at most one partial line behind
+        self.file.write_all(&line)?;
+        self.file.flush()?;
+
+        let tip = Tip {
+            height: record.height,
+            hash: record.hash,
+        };
+        self.tip = Some(tip);
+        Ok(tip)
+    }
+}
+
+/// Stream committed blocks from `from` into `archive`, calling `progress`
+/// with the new tip after each one.
+///
+/// Stops after `limit` blocks, or never if it is `None`.
+///
+/// # Errors
+/// Fails if the stream breaks or a block doesn't extend the archived chain.
+pub fn archive_blocks(
+    client: &Client,
+    archive: &mut BlockArchive,
+    from: NonZeroU64,
+    limit: Option<u64>,
+    mut progress: impl FnMut(Tip),
+) -> Result<u64> {
+    let mut archived = 0_u64;
+    if limit == Some(0) {
+        return Ok(archived);
+    }
+    for block in client.listen_for_blocks(from)? {
+        let tip = archive.append(&block?)?;
+        archived += 1;
+        progress(tip);
+        if limit == Some(archived) {
+            break;
+        }
+    }
+    Ok(archived)
+}
@@ -0,0 +1,141 @@
+//! Submitting many instructions in as few transactions as the limits allow.
+//!
+//! A transaction holds at most `TRANSACTION_LIMITS.max_instruction_number`
+//! instructions. [`BatchSubmitter`] splits a stream of instructions into
+//! transactions of that size and submits them one after another or from
+//! several threads at once, waiting for each to be committed.
+
+use std::{num::NonZeroUsize, sync::Mutex, thread};
+
+use eyre::{Result, WrapErr};
+use iroha_client::client::Client;
+use iroha_data_model::{prelude::*, transaction::TransactionLimits};
+
+/// How the batches are submitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// One after another, in order.
+    Sequential,
+    /// From this many threads at once. Batches may be committed out of order,
+    /// so they shouldn't depend on one another.
+    Concurrent(NonZeroUsize),
+}
+
+/// Outcome of a single batch.
+#[derive(Debug)]
+pub struct BatchResult {
+    /// Position of the batch in the stream.
+    pub index: usize,
+    /// Number of instructions in the batch.
+    pub instructions: usize,
+    /// Hash of the committed transaction, or why it failed.
+    pub outcome: Result<HashOf<VersionedSignedTransaction>>,
+}
+
+/// Split `instructions` into batches of at most `max_size`.
+pub fn batches<I>(
+    instructions: I,
+    max_size: NonZeroUsize,
+) -> impl Iterator<Item = Vec<InstructionBox>>
+where
+    I: IntoIterator<Item = InstructionBox>,
+{
+    let mut instructions = instructions.into_iter();
+    std::iter::from_fn(move || {
+        let batch: Vec<_> = instructions.by_ref().take(max_size.get()).collect();
+        (!batch.is_empty()).then_some(batch)
+    })
+}
+
+/// Packs instructions into transactions and submits them.
+#[derive(Debug, Clone)]
+pub struct BatchSubmitter {
+    client: Client,
+    max_size: NonZeroUsize,
+    mode: Mode,
+}
+
+impl BatchSubmitter {
+    /// Submit with `client`, packing as many instructions per transaction
+    /// as `limits` allow, one batch after another.
+    ///
+    /// # Errors
+    /// Fails if the limits don't allow any instructions.
+    pub fn new(client: Client, limits: &TransactionLimits) -> Result<Self> {
+        let max_size = usize::try_from(limits.max_instruction_number)
+            .ok()
+            .and_then(NonZeroUsize::new)
+            .ok_or_else(|| eyre::eyre!("`max_instruction_number` must be positive"))?;
+        Ok(Self {
+            client,
+            max_size,
+            mode: Mode::Sequential,
+        })
+    }
+
+    /// Put at most `max_size` instructions in a transaction, if that's below the limits.
+    #[must_use]
+    pub fn max_batch_size(mut self, max_size: NonZeroUsize) -> Self {
+        self.max_size = self.max_size.min(max_size);
+        self
+    }
+
+    /// Submit the batches as `mode` says.
+    #[must_use]
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// The most instructions that go into a transaction.
+    pub fn batch_size(&self) -> NonZeroUsize {
+        self.max_size
+    }
+
+    /// Submit `instructions` and wait for every batch to finish.
+    ///
+    /// A failed batch doesn't stop the others, check the results.
+    /// The results are ordered by [`BatchResult::index`].
+    pub fn submit<I>(&self, instructions: I) -> Vec<BatchResult>
+    where
+        I: IntoIterator<Item = InstructionBox>,
+        I::IntoIter: Send,
+    {
+        let batches = batches(instructions, self.max_size).enumerate();
+        let mut results: Vec<BatchResult> = match self.mode {
+            Mode::Sequential => batches
+                .map(|(index, batch)| self.submit_batch(index, batch))
+                .collect(),
+            Mode::Concurrent(threads) => {
+                let batches = Mutex::new(batches);
+                let results = Mutex::new(Vec::new());
+                thread::scope(|scope| {
+                    for _ in 0..threads.get() {
+                        scope.spawn(|| loop {
+                            // Don't hold the lock while submitting
+                            let next = batches.lock().expect("Not poisoned").next();
+                            let Some((index, batch)) = next else { break };
+                            let result = self.submit_batch(index, batch);
+                            results.lock().expect("Not poisoned").push(result);
+                        });
+                    }
+                });
+                results.into_inner().expect("Not poisoned")
+            }
+        };
+        results.sort_by_key(|result| result.index);
+        results
+    }
+
+    fn submit_batch(&self, index: usize, batch: Vec<InstructionBox>) -> BatchResult {
+        let instructions = batch.len();
+        let outcome = self.client.submit_all_blocking(batch).wrap_err(format!(
+            "Batch {index} of {instructions} instruction(s) failed"
+        ));
+        BatchResult {
+            index,
+            instructions,
+            outcome,
+        }
+    }
+}
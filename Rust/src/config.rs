@@ -0,0 +1,344 @@
+//! Layered client configuration.
+//!
+//! The final [`Configuration`] is assembled from up to three layers, later
+//! layers overriding earlier ones key by key:
+//!
+//! 1. a base JSON file, usually `config.json`;
+//! 2. an optional profile file with the same layout, which only needs to
+//!    contain the keys it changes (e.g. `TORII_API_URL` of another peer);
+//! 3. `IROHA_*` environment variables, e.g. `IROHA_TORII_API_URL` or
+//!    `IROHA_ACCOUNT_ID`. Nested keys are separated by a double underscore:
+//!    `IROHA_BASIC_AUTH__WEB_LOGIN`. Variables that don't name a key of
+//!    [`ConfigurationProxy`], such as [`CONFIG_PATH_ENV`], are ignored.
+//!    A value overriding a string is taken as is; other values are parsed
+//!    as JSON, falling back to a string.
+//!
+//! Every leaf value remembers the layer it came from, see [`LoadedConfig::sources`].
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+use eyre::{Result, WrapErr};
+use iroha_client::client::Client;
+use iroha_config::client::{Configuration, ConfigurationProxy};
+use iroha_config_base::proxy::Builder;
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde_json::{Map, Value};
+
+/// Prefix of the environment variables that override configuration keys.
+pub const ENV_PREFIX: &str = "IROHA_";
+/// Environment variable holding the path of the base configuration file.
+pub const CONFIG_PATH_ENV: &str = "IROHA_CONFIG";
+/// Environment variable holding the path of the profile file.
+pub const PROFILE_PATH_ENV: &str = "IROHA_PROFILE";
+
+/// Separator of nested keys in environment variable names.
+const ENV_NESTING_SEPARATOR: &str = "__";
+
+/// The layer a configuration value was taken from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Layer {
+    /// The base configuration file.
+    Base(PathBuf),
+    /// The profile file.
+    Profile(PathBuf),
+    /// An environment variable with the given name.
+    Env(String),
+}
+
+impl fmt::Display for Layer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Base(path) => write!(f, "base file `{}`", path.display()),
+            Self::Profile(path) => write!(f, "profile file `{}`", path.display()),
+            Self::Env(var) => write!(f, "environment variable `{var}`"),
+        }
+    }
+}
+
+/// Builder for a layered client configuration.
+#[derive(Debug, Clone)]
+pub struct ConfigLoader {
+    base: PathBuf,
+    profile: Option<PathBuf>,
+    env_prefix: String,
+}
+
+impl ConfigLoader {
+    /// Start loading from the base file at `base`.
+    pub fn new(base: impl AsRef<Path>) -> Self {
+        Self {
+            base: base.as_ref().to_path_buf(),
+            profile: None,
+            env_prefix: ENV_PREFIX.to_owned(),
+        }
+    }
+
+    /// Merge the profile file at `path` on top of the base file.
+    #[must_use]
+    pub fn profile(mut self, path: impl AsRef<Path>) -> Self {
+        self.profile = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Take the profile file path from [`PROFILE_PATH_ENV`], if it is set.
+    #[must_use]
+    pub fn profile_from_env(mut self) -> Self {
+        if let Some(path) = std::env::var_os(PROFILE_PATH_ENV) {
+            self.profile = Some(path.into());
+        }
+        self
+    }
+
+    /// Use another prefix for the overriding environment variables.
+    #[must_use]
+    pub fn env_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.env_prefix = prefix.into();
+        self
+    }
+
+    /// Merge all the layers and build the configuration.
+    ///
+    /// # Errors
+    /// Fails if a file can't be read or parsed, or if the merged
+    /// configuration is incomplete or invalid.
+    pub fn load(&self) -> Result<LoadedConfig> {
+        self.load_from_vars(std::env::vars())
+    }
+
+    /// Same as [`Self::load`], but reads the overrides from `vars`
+    /// instead of the process environment.
+    ///
+    /// # Errors
+    /// See [`Self::load`].
+    pub fn load_from_vars(
+        &self,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<LoadedConfig> {
+        let mut merged = Map::new();
+        let mut sources = BTreeMap::new();
+
+        let base = read_object(&self.base)?;
+        merge(
+            &mut merged,
+            base,
+            "",
+            &Layer::Base(self.base.clone()),
+            &mut sources,
+        );
+
+        if let Some(profile) = &self.profile {
+            let layer = read_object(profile)?;
+            merge(
+                &mut merged,
+                layer,
+                "",
+                &Layer::Profile(profile.clone()),
+                &mut sources,
+            );
+        }
+
+        let keys = config_keys();
+        let mut vars: Vec<_> = vars
+            .into_iter()
+            .filter_map(|(name, value)| {
+                let key = name.strip_prefix(&self.env_prefix)?.to_owned();
+                let top_level = key.split(ENV_NESTING_SEPARATOR).next()?;
+                keys.iter()
+                    .any(|known| known.eq_ignore_ascii_case(top_level))
+                    .then_some((name, key, value))
+            })
+            .collect();
+        // Apply the overrides in a stable order
+        vars.sort();
+        for (name, key, value) in vars {
+            let path: Vec<_> = key.split(ENV_NESTING_SEPARATOR).collect();
+            set_path(&mut merged, &path, value, &Layer::Env(name), &mut sources);
+        }
+
+        let proxy: ConfigurationProxy = serde_json::from_value(Value::Object(merged))
+            .wrap_err("Failed to parse the merged client configuration")?;
+        let config = proxy
+            .build()
+            .wrap_err("Merged client configuration is incomplete or invalid")?;
+
+        Ok(LoadedConfig { config, sources })
+    }
+}
+
+/// A configuration together with the origin of each of its values.
+#[derive(Debug, Clone)]
+pub struct LoadedConfig {
+    /// The merged configuration.
+    pub config: Configuration,
+    /// The layer each leaf value came from, keyed by its dotted path,
+    /// e.g. `TORII_API_URL` or `BASIC_AUTH.web_login`.
+    pub sources: BTreeMap<String, Layer>,
+}
+
+impl LoadedConfig {
+    /// The layer the value at the dotted `key` came from.
+    pub fn source_of(&self, key: &str) -> Option<&Layer> {
+        self.sources.get(key)
+    }
+
+    /// Construct a client from the merged configuration.
+    ///
+    /// # Errors
+    /// Fails if the client can't be created from the configuration.
+    pub fn client(&self) -> Result<Client> {
+        Client::new(&self.config)
+    }
+}
+
+fn read_object(path: &Path) -> Result<Map<String, Value>> {
+    let file = File::open(path).wrap_err(format!(
+        "Unable to load the configuration file at `{}`",
+        path.display()
+    ))?;
+    match serde_json::from_reader(file).wrap_err(format!("Failed to parse `{}`", path.display()))? {
+        Value::Object(map) => Ok(map),
+        _ => Err(eyre::eyre!("`{}` is not a JSON object", path.display())),
+    }
+}
+
+fn join(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{prefix}.{key}")
+    }
+}
+
+/// Forget the sources of `prefix` and everything nested in it.
+fn forget(sources: &mut BTreeMap<String, Layer>, prefix: &str) {
+    let nested = format!("{prefix}.");
+    sources.retain(|key, _| key != prefix && !key.starts_with(&nested));
+}
+
+/// Record `layer` as the source of every leaf in `value`.
+fn record(sources: &mut BTreeMap<String, Layer>, prefix: &str, value: &Value, layer: &Layer) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                record(sources, &join(prefix, key), value, layer);
+            }
+        }
+        _ => {
+            sources.insert(prefix.to_owned(), layer.clone());
+        }
+    }
+}
+
+/// Deep-merge `layer` into `target`, replacing leaves.
+fn merge(
+    target: &mut Map<String, Value>,
+    layer: Map<String, Value>,
+    prefix: &str,
+    source: &Layer,
+    sources: &mut BTreeMap<String, Layer>,
+) {
+    for (key, value) in layer {
+        let path = join(prefix, &key);
+        match (target.get_mut(&key), value) {
+            (Some(Value::Object(existing)), Value::Object(nested)) => {
+                merge(existing, nested, &path, source, sources);
+            }
+            (_, value) => {
+                forget(sources, &path);
+                record(sources, &path, &value, source);
+                target.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Set the value at `path`, matching the existing keys case-insensitively.
+///
+/// Environment variables are conventionally upper-case, while the nested
+/// keys of the configuration (e.g. `BASIC_AUTH.web_login`) are not.
+fn set_path(
+    target: &mut Map<String, Value>,
+    path: &[&str],
+    value: String,
+    source: &Layer,
+    sources: &mut BTreeMap<String, Layer>,
+) {
+    fn resolve(map: &Map<String, Value>, key: &str, top_level: bool) -> String {
+        map.keys()
+            .find(|existing| existing.eq_ignore_ascii_case(key))
+            .cloned()
+            .unwrap_or_else(|| {
+                if top_level {
+                    key.to_ascii_uppercase()
+                } else {
+                    key.to_ascii_lowercase()
+                }
+            })
+    }
+
+    let mut map = target;
+    let mut prefix = String::new();
+    let (last, parents) = path.split_last().expect("`split` yields at least one key");
+    for (depth, key) in parents.iter().enumerate() {
+        let key = resolve(map, key, depth == 0);
+        prefix = join(&prefix, &key);
+        let entry = map.entry(key).or_insert_with(|| Value::Object(Map::new()));
+        if !entry.is_object() {
+            forget(sources, &prefix);
+            *entry = Value::Object(Map::new());
+        }
+        map = entry.as_object_mut().expect("Ensured above");
+    }
+    let key = resolve(map, last, parents.is_empty());
+    let value = match map.get(&key) {
+        // `"123456"` is a valid password, not a number
+        Some(Value::String(_)) => Value::String(value),
+        _ => serde_json::from_str(&value).unwrap_or(Value::String(value)),
+    };
+    let path = join(&prefix, &key);
+    forget(sources, &path);
+    record(sources, &path, &value, source);
+    map.insert(key, value);
+}
+
+/// Names of the top-level keys of [`ConfigurationProxy`].
+///
+/// They are taken from its `Deserialize` implementation, which hands them
+/// to the deserializer before reading anything.
+fn config_keys() -> &'static [&'static str] {
+    struct FieldNames<'a>(&'a mut &'static [&'static str]);
+
+    impl<'de> Deserializer<'de> for FieldNames<'_> {
+        type Error = de::value::Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Self::Error> {
+            Err(de::Error::custom("expected a struct"))
+        }
+
+        fn deserialize_struct<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            fields: &'static [&'static str],
+            _visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            *self.0 = fields;
+            Err(de::Error::custom("only the field names are needed"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map enum identifier ignored_any
+        }
+    }
+
+    let mut fields: &'static [&'static str] = &[];
+    // Always fails, the names are all we want
+    let _ = ConfigurationProxy::deserialize(FieldNames(&mut fields));
+    fields
+}
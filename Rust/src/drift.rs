@@ -0,0 +1,423 @@
+//! Comparing a live ledger with the state expected by a fixture.
+//!
+//! [`detect`] fetches every domain, account, asset definition and asset of
+//! the ledger and lists how they differ from a [`Fixture`]: entities that
+//! are missing or extra, balances that don't match and metadata entries
+//! that were added, removed or changed. Domains outside of the fixture are
+//! reported as extra, unless they are ignored; their contents aren't
+//! compared.
+//!
+//! Balances in the fixture are compared as they are, so a fixture that was
+//! applied before transactions moved the assets reports them as mismatches.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use eyre::{Result, WrapErr};
+use iroha_client::client::Client;
+use iroha_data_model::{
+    metadata::Metadata,
+    prelude::*,
+    query::{
+        account::model::FindAllAccounts,
+        asset::model::{FindAllAssets, FindAllAssetsDefinitions},
+        domain::model::FindAllDomains,
+    },
+};
+use serde::Serialize;
+
+use crate::{
+    fixture::{Fixture, Scalar},
+    query::PagedQuery,
+};
+
+/// Kind of a ledger entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Entity {
+    /// A domain.
+    Domain,
+    /// An account.
+    Account,
+    /// An asset definition.
+    AssetDefinition,
+    /// An asset held by an account.
+    Asset,
+}
+
+impl fmt::Display for Entity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Domain => "domain",
+            Self::Account => "account",
+            Self::AssetDefinition => "asset definition",
+            Self::Asset => "asset",
+        })
+    }
+}
+
+/// A single difference between the ledger and the fixture.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Change {
+    /// In the fixture, but not on the ledger.
+    Missing {
+        /// Kind of the entity.
+        entity: Entity,
+        /// Id of the entity.
+        id: String,
+    },
+    /// On the ledger, but not in the fixture.
+    Extra {
+        /// Kind of the entity.
+        entity: Entity,
+        /// Id of the entity.
+        id: String,
+    },
+    /// An asset with another amount than expected.
+    Balance {
+        /// Id of the asset.
+        id: String,
+        /// The amount in the fixture.
+        expected: String,
+        /// The amount on the ledger.
+        actual: String,
+    },
+    /// A field of an asset definition with another value than expected.
+    Definition {
+        /// Id of the asset definition.
+        id: String,
+        /// Name of the field.
+        field: String,
+        /// The value in the fixture.
+        expected: String,
+        /// The value on the ledger.
+        actual: String,
+    },
+    /// A metadata entry that was added, removed or changed.
+    Metadata {
+        /// Kind of the entity.
+        entity: Entity,
+        /// Id of the entity.
+        id: String,
+        /// Key of the entry.
+        key: String,
+        /// The value in the fixture, if any.
+        expected: Option<String>,
+        /// The value on the ledger, if any.
+        actual: Option<String>,
+    },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { entity, id } => write!(f, "missing {entity} `{id}`"),
+            Self::Extra { entity, id } => write!(f, "extra {entity} `{id}`"),
+            Self::Balance {
+                id,
+                expected,
+                actual,
+            } => write!(f, "asset `{id}` holds {actual}, expected {expected}"),
+            Self::Definition {
+                id,
+                field,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "asset definition `{id}` has {field} {actual}, expected {expected}"
+            ),
+            Self::Metadata {
+                entity,
+                id,
+                key,
+                expected,
+                actual,
+            } => {
+                write!(f, "{entity} `{id}` metadata `{key}`: ")?;
+                match (expected, actual) {
+                    (Some(expected), Some(actual)) => write!(f, "{actual}, expected {expected}"),
+                    (Some(expected), None) => write!(f, "removed, expected {expected}"),
+                    (None, Some(actual)) => write!(f, "added {actual}"),
+                    (None, None) => write!(f, "unchanged"),
+                }
+            }
+        }
+    }
+}
+
+/// All the differences between the ledger and the fixture.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Drift {
+    /// The differences, in the order of the fixture, extra domains last.
+    pub changes: Vec<Change>,
+}
+
+impl Drift {
+    /// Whether the ledger matches the fixture.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// The drift as pretty-printed JSON.
+    ///
+    /// # Errors
+    /// Never fails in practice, the drift is always serializable.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "The ledger matches the fixture");
+        }
+        write!(f, "{} difference(s):", self.changes.len())?;
+        for change in &self.changes {
+            write!(f, "\n  {change}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The entities of the ledger, by id.
+struct Ledger {
+    domains: BTreeMap<DomainId, Domain>,
+    accounts: BTreeMap<AccountId, Account>,
+    definitions: BTreeMap<AssetDefinitionId, AssetDefinition>,
+    assets: BTreeMap<AssetId, Asset>,
+}
+
+impl Ledger {
+    fn fetch(client: &Client) -> Result<Self> {
+        let domains = PagedQuery::new(FindAllDomains)
+            .iter::<Domain>(client)
+            .map(|domain| domain.map(|domain| (domain.id.clone(), domain)))
+            .collect::<Result<_>>()
+            .wrap_err("Failed to fetch the domains")?;
+        let accounts = PagedQuery::new(FindAllAccounts)
+            .iter::<Account>(client)
+            .map(|account| account.map(|account| (account.id.clone(), account)))
+            .collect::<Result<_>>()
+            .wrap_err("Failed to fetch the accounts")?;
+        let definitions = PagedQuery::new(FindAllAssetsDefinitions)
+            .iter::<AssetDefinition>(client)
+            .map(|definition| definition.map(|definition| (definition.id.clone(), definition)))
+            .collect::<Result<_>>()
+            .wrap_err("Failed to fetch the asset definitions")?;
+        let assets = PagedQuery::new(FindAllAssets)
+            .iter::<Asset>(client)
+            .map(|asset| asset.map(|asset| (asset.id.clone(), asset)))
+            .collect::<Result<_>>()
+            .wrap_err("Failed to fetch the assets")?;
+        Ok(Self {
+            domains,
+            accounts,
+            definitions,
+            assets,
+        })
+    }
+}
+
+/// Compare the ledger with `fixture`. Domains in `ignored` aren't reported
+/// as extra, which is useful for the ones created by the genesis.
+///
+/// # Errors
+/// Fails if a query fails or the fixture has an invalid balance.
+pub fn detect(client: &Client, fixture: &Fixture, ignored: &[DomainId]) -> Result<Drift> {
+    let ledger = Ledger::fetch(client)?;
+    let mut changes = Vec::new();
+
+    for domain in &fixture.domains {
+        let Some(existing) = ledger.domains.get(&domain.id) else {
+            changes.push(Change::Missing {
+                entity: Entity::Domain,
+                id: domain.id.to_string(),
+            });
+            continue;
+        };
+        compare_metadata(
+            Entity::Domain,
+            &domain.id,
+            &domain.metadata,
+            &existing.metadata,
+            &mut changes,
+        );
+
+        // Asset definitions
+        let mut expected_definitions = BTreeSet::new();
+        for definition in &domain.asset_definitions {
+            let id = AssetDefinitionId::new(definition.name.clone(), domain.id.clone());
+            expected_definitions.insert(id.clone());
+            let Some(existing) = ledger.definitions.get(&id) else {
+                changes.push(Change::Missing {
+                    entity: Entity::AssetDefinition,
+                    id: id.to_string(),
+                });
+                continue;
+            };
+            if existing.value_type != definition.value_type {
+                changes.push(Change::Definition {
+                    id: id.to_string(),
+                    field: "value_type".to_owned(),
+                    expected: format!("{:?}", definition.value_type),
+                    actual: format!("{:?}", existing.value_type),
+                });
+            }
+            // A once-mintable asset becomes non-mintable when it's minted
+            let mintable_matches = existing.mintable == definition.mintable
+                || (definition.mintable, existing.mintable) == (Mintable::Once, Mintable::Not);
+            if !mintable_matches {
+                changes.push(Change::Definition {
+                    id: id.to_string(),
+                    field: "mintable".to_owned(),
+                    expected: format!("{:?}", definition.mintable),
+                    actual: format!("{:?}", existing.mintable),
+                });
+            }
+            compare_metadata(
+                Entity::AssetDefinition,
+                &id,
+                &definition.metadata,
+                &existing.metadata,
+                &mut changes,
+            );
+        }
+        for id in ledger.definitions.keys() {
+            if id.domain_id == domain.id && !expected_definitions.contains(id) {
+                changes.push(Change::Extra {
+                    entity: Entity::AssetDefinition,
+                    id: id.to_string(),
+                });
+            }
+        }
+
+        // Accounts and their assets
+        let mut expected_accounts = BTreeSet::new();
+        for account in &domain.accounts {
+            let id = AccountId::new(account.name.clone(), domain.id.clone());
+            expected_accounts.insert(id.clone());
+            let Some(existing) = ledger.accounts.get(&id) else {
+                changes.push(Change::Missing {
+                    entity: Entity::Account,
+                    id: id.to_string(),
+                });
+                continue;
+            };
+            compare_metadata(
+                Entity::Account,
+                &id,
+                &account.metadata,
+                &existing.metadata,
+                &mut changes,
+            );
+
+            for (definition_id, amount) in &account.balances {
+                let asset_id = AssetId::new(definition_id.clone(), id.clone());
+                let Some(asset) = ledger.assets.get(&asset_id) else {
+                    changes.push(Change::Missing {
+                        entity: Entity::Asset,
+                        id: asset_id.to_string(),
+                    });
+                    continue;
+                };
+                compare_balance(&asset_id, amount, &asset.value, &mut changes)?;
+            }
+            for asset_id in ledger.assets.keys() {
+                if asset_id.account_id == id
+                    && !account.balances.contains_key(&asset_id.definition_id)
+                {
+                    changes.push(Change::Extra {
+                        entity: Entity::Asset,
+                        id: asset_id.to_string(),
+                    });
+                }
+            }
+        }
+        for id in ledger.accounts.keys() {
+            if id.domain_id == domain.id && !expected_accounts.contains(id) {
+                changes.push(Change::Extra {
+                    entity: Entity::Account,
+                    id: id.to_string(),
+                });
+            }
+        }
+    }
+
+    for id in ledger.domains.keys() {
+        let expected = fixture.domains.iter().any(|domain| &domain.id == id);
+        if !expected && !ignored.contains(id) {
+            changes.push(Change::Extra {
+                entity: Entity::Domain,
+                id: id.to_string(),
+            });
+        }
+    }
+
+    Ok(Drift { changes })
+}
+
+fn compare_balance(
+    asset_id: &AssetId,
+    expected: &Scalar,
+    actual: &AssetValue,
+    changes: &mut Vec<Change>,
+) -> Result<()> {
+    let expected = expected
+        .to_asset_value(actual.value_type())
+        .wrap_err(format!("Invalid balance of `{asset_id}`"))?;
+    if &expected != actual {
+        changes.push(Change::Balance {
+            id: asset_id.to_string(),
+            expected: amount(&expected),
+            actual: amount(actual),
+        });
+    }
+    Ok(())
+}
+
+fn amount(value: &AssetValue) -> String {
+    match value {
+        AssetValue::Quantity(value) => value.to_string(),
+        AssetValue::BigQuantity(value) => value.to_string(),
+        AssetValue::Fixed(value) => format!("{value:?}"),
+        AssetValue::Store(value) => format!("{value:?}"),
+    }
+}
+
+/// Every key that is only in one of the metadata or has different values.
+fn compare_metadata(
+    entity: Entity,
+    id: &impl fmt::Display,
+    expected: &BTreeMap<Name, Scalar>,
+    actual: &Metadata,
+    changes: &mut Vec<Change>,
+) {
+    let mut change = |key: &Name, expected: Option<&Value>, actual: Option<&Value>| {
+        changes.push(Change::Metadata {
+            entity,
+            id: id.to_string(),
+            key: key.to_string(),
+            expected: expected.map(|value| format!("{value:?}")),
+            actual: actual.map(|value| format!("{value:?}")),
+        });
+    };
+
+    for (key, value) in expected {
+        // An entry that can't be a metadata value can't match either
+        let expected = value.to_metadata_value().ok();
+        let existing = actual.get(key);
+        if expected.as_ref() != existing {
+            change(key, expected.as_ref(), existing);
+        }
+    }
+    for (key, value) in actual.iter() {
+        if !expected.contains_key(key) {
+            change(key, None, Some(value));
+        }
+    }
+}
@@ -0,0 +1,278 @@
+//! Registering domains, accounts, asset definitions and roles only if they are missing.
+//!
+//! Registering an entity that already exists is rejected, so scripts that
+//! register things can't be run twice. The `ensure_*` functions query the
+//! entity first and register it only when it's not found. When it exists,
+//! they compare it with the requested one and report every [`Difference`],
+//! leaving the existing entity as it is.
+
+use std::fmt;
+
+use eyre::{Result, WrapErr};
+use iroha_client::client::{Client, ClientQueryError};
+use iroha_data_model::{
+    metadata::Metadata,
+    prelude::*,
+    query::{
+        account::model::FindAccountById,
+        asset::model::FindAssetDefinitionById,
+        domain::model::FindDomainById,
+        error::{FindError, QueryExecutionFail},
+        role::model::FindRoleByRoleId,
+    },
+    ValidationFail,
+};
+
+/// What an `ensure_*` call found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Ensured {
+    /// The entity was missing and has been registered.
+    Registered,
+    /// The entity exists and matches the requested one.
+    Exists,
+    /// The entity exists but doesn't match the requested one.
+    Differs(Vec<Difference>),
+}
+
+impl Ensured {
+    /// Whether the entity has been registered by this call.
+    pub fn is_registered(&self) -> bool {
+        matches!(self, Self::Registered)
+    }
+}
+
+impl fmt::Display for Ensured {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Registered => write!(f, "registered"),
+            Self::Exists => write!(f, "already exists"),
+            Self::Differs(differences) => {
+                write!(f, "already exists, but differs:")?;
+                for difference in differences {
+                    write!(f, " {difference};")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A field of an existing entity that doesn't match the requested value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Difference {
+    /// Name of the field, `metadata.<key>` for metadata entries.
+    pub field: String,
+    /// The value on the ledger.
+    pub existing: String,
+    /// The requested value.
+    pub requested: String,
+}
+
+impl Difference {
+    fn new(
+        field: impl Into<String>,
+        existing: impl fmt::Debug,
+        requested: impl fmt::Debug,
+    ) -> Self {
+        Self {
+            field: field.into(),
+            existing: format!("{existing:?}"),
+            requested: format!("{requested:?}"),
+        }
+    }
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is {}, not {}",
+            self.field, self.existing, self.requested
+        )
+    }
+}
+
+/// Register `domain` unless a domain with its id exists.
+///
+/// The logo and the requested metadata entries are compared.
+///
+/// # Errors
+/// Fails if the query or the registration fails.
+pub fn ensure_domain(client: &Client, domain: NewDomain) -> Result<Ensured> {
+    let id = domain.id.clone();
+    let existing = match client.request(FindDomainById {
+        id: id.clone().into(),
+    }) {
+        Ok(existing) => existing,
+        Err(ClientQueryError::Validation(ValidationFail::QueryFailed(
+            QueryExecutionFail::Find(FindError::Domain(_)),
+        ))) => return register(client, RegisterBox::new(domain), &id),
+        Err(error) => return Err(error).wrap_err(format!("Failed to query domain `{id}`")),
+    };
+
+    let mut differences = Vec::new();
+    if existing.logo != domain.logo {
+        differences.push(Difference::new("logo", &existing.logo, &domain.logo));
+    }
+    compare_metadata(&existing.metadata, &domain.metadata, &mut differences);
+    Ok(outcome(differences))
+}
+
+/// Register `account` unless an account with its id exists.
+///
+/// The requested signatories must all be signatories of the existing account.
+/// The requested metadata entries are compared too.
+///
+/// # Errors
+/// Fails if the query or the registration fails.
+pub fn ensure_account(client: &Client, account: NewAccount) -> Result<Ensured> {
+    let id = account.id.clone();
+    let existing = match client.request(FindAccountById {
+        id: id.clone().into(),
+    }) {
+        Ok(existing) => existing,
+        Err(ClientQueryError::Validation(ValidationFail::QueryFailed(
+            QueryExecutionFail::Find(FindError::Account(_)),
+        ))) => return register(client, RegisterBox::new(account), &id),
+        Err(error) => return Err(error).wrap_err(format!("Failed to query account `{id}`")),
+    };
+
+    let mut differences = Vec::new();
+    let has_signatories = account
+        .signatories
+        .iter()
+        .all(|key| existing.signatories.contains(key));
+    if !has_signatories {
+        differences.push(Difference::new(
+            "signatories",
+            &existing.signatories,
+            &account.signatories,
+        ));
+    }
+    compare_metadata(&existing.metadata, &account.metadata, &mut differences);
+    Ok(outcome(differences))
+}
+
+/// Register `definition` unless an asset definition with its id exists.
+///
+/// The value type, the mintability, the logo and the requested metadata
+/// entries are compared. A once-mintable asset becomes non-mintable after
+/// its first mint, so an existing non-mintable definition matches a
+/// requested once-mintable one.
+///
+/// # Errors
+/// Fails if the query or the registration fails.
+pub fn ensure_asset_definition(client: &Client, definition: NewAssetDefinition) -> Result<Ensured> {
+    let id = definition.id.clone();
+    let existing = match client.request(FindAssetDefinitionById {
+        id: id.clone().into(),
+    }) {
+        Ok(existing) => existing,
+        Err(ClientQueryError::Validation(ValidationFail::QueryFailed(
+            QueryExecutionFail::Find(FindError::AssetDefinition(_)),
+        ))) => return register(client, RegisterBox::new(definition), &id),
+        Err(error) => {
+            return Err(error).wrap_err(format!("Failed to query asset definition `{id}`"))
+        }
+    };
+
+    let mut differences = Vec::new();
+    if existing.value_type != definition.value_type {
+        differences.push(Difference::new(
+            "value_type",
+            existing.value_type,
+            definition.value_type,
+        ));
+    }
+    let mintable_matches = match (existing.mintable, definition.mintable) {
+        (Mintable::Not, Mintable::Once) => true,
+        (existing, requested) => existing == requested,
+    };
+    if !mintable_matches {
+        differences.push(Difference::new(
+            "mintable",
+            existing.mintable,
+            definition.mintable,
+        ));
+    }
+    if existing.logo != definition.logo {
+        differences.push(Difference::new("logo", &existing.logo, &definition.logo));
+    }
+    compare_metadata(&existing.metadata, &definition.metadata, &mut differences);
+    Ok(outcome(differences))
+}
+
+/// Register the role `id` with `permissions` unless a role with this id exists.
+///
+/// The requested permissions must all be permissions of the existing role.
+///
+/// # Errors
+/// Fails if the query or the registration fails.
+pub fn ensure_role(
+    client: &Client,
+    id: RoleId,
+    permissions: impl IntoIterator<Item = PermissionToken>,
+) -> Result<Ensured> {
+    let permissions: Vec<_> = permissions.into_iter().collect();
+    let existing = match client.request(FindRoleByRoleId {
+        id: id.clone().into(),
+    }) {
+        Ok(existing) => existing,
+        Err(ClientQueryError::Validation(ValidationFail::QueryFailed(
+            QueryExecutionFail::Find(FindError::Role(_)),
+        ))) => {
+            let role = permissions
+                .into_iter()
+                .fold(Role::new(id.clone()), |role, permission| {
+                    role.add_permission(permission)
+                });
+            return register(client, RegisterBox::new(role), &id);
+        }
+        Err(error) => return Err(error).wrap_err(format!("Failed to query role `{id}`")),
+    };
+
+    let mut differences = Vec::new();
+    let existing_permissions: Vec<_> = existing.permissions().collect();
+    let has_permissions = permissions
+        .iter()
+        .all(|permission| existing_permissions.contains(&permission));
+    if !has_permissions {
+        differences.push(Difference::new(
+            "permissions",
+            &existing_permissions,
+            &permissions,
+        ));
+    }
+    Ok(outcome(differences))
+}
+
+/// Entries of `requested` that are missing from `existing` or have other values.
+/// Extra entries of `existing` are fine, they may have been set later.
+fn compare_metadata(existing: &Metadata, requested: &Metadata, differences: &mut Vec<Difference>) {
+    for (key, value) in requested.iter() {
+        let existing_value = existing.get(key);
+        if existing_value != Some(value) {
+            differences.push(Difference::new(
+                format!("metadata.{key}"),
+                existing_value,
+                value,
+            ));
+        }
+    }
+}
+
+fn outcome(differences: Vec<Difference>) -> Ensured {
+    if differences.is_empty() {
+        Ensured::Exists
+    } else {
+        Ensured::Differs(differences)
+    }
+}
+
+fn register(client: &Client, register: RegisterBox, id: &impl fmt::Display) -> Result<Ensured> {
+    let register: InstructionBox = register.into();
+    client
+        .submit_blocking(register)
+        .wrap_err(format!("Failed to register `{id}`"))?;
+    Ok(Ensured::Registered)
+}
@@ -0,0 +1,339 @@
+//! Subscribing to events with automatic reconnection.
+//!
+//! [`EventListener`] wraps [`Client::listen_for_events`]: when the stream
+//! breaks, it reconnects with an exponential backoff and carries on, handing
+//! the events either to a callback ([`EventListener::listen`]) or to an
+//! asynchronous [`Stream`] ([`EventListener::into_stream`]).
+//!
+//! Events that occur while the listener is reconnecting are not replayed.
+//! When the reconnection attempts are exhausted, the stream and the channel
+//! end with the error. Once the stream or the channel is dropped, the
+//! connection is closed right away, without waiting for another event.
+
+use std::{
+    future::Future,
+    ops::{ControlFlow, Deref},
+    pin::Pin,
+    sync::mpsc as std_mpsc,
+    task::{Context, Poll},
+    thread,
+    time::Duration,
+};
+
+use eyre::{Result, WrapErr};
+use futures::{
+    channel::{mpsc, oneshot},
+    future::{self, Either},
+    FutureExt, Stream, StreamExt,
+};
+use iroha_client::client::Client;
+use iroha_crypto::Hash;
+use iroha_data_model::prelude::*;
+use tokio::runtime::{Builder, Runtime};
+
+/// A single subscription to the events of a peer.
+type Events = Pin<Box<dyn Stream<Item = Result<Event>> + Send>>;
+
+/// Events about the changes of any asset held by the accounts of `domain`.
+pub fn asset_changes_in_domain(domain: DomainId) -> FilterBox {
+    let account_assets = AccountFilter::new(
+        FilterOpt::AcceptAll,
+        FilterOpt::BySome(AccountEventFilter::ByAsset(FilterOpt::AcceptAll)),
+    );
+    let domain_filter = DomainFilter::new(
+        FilterOpt::BySome(OriginFilter::new(domain)),
+        FilterOpt::BySome(DomainEventFilter::ByAccount(FilterOpt::BySome(
+            account_assets,
+        ))),
+    );
+    FilterBox::Data(FilterOpt::BySome(DataEntityFilter::ByDomain(
+        FilterOpt::BySome(domain_filter),
+    )))
+}
+
+/// Status changes of the transaction with the given `hash`.
+pub fn transaction_status(hash: impl Into<Hash>) -> FilterBox {
+    FilterBox::Pipeline(
+        PipelineEventFilter::new()
+            .entity_kind(PipelineEntityKind::Transaction)
+            .hash(hash.into()),
+    )
+}
+
+/// Status changes of all transactions.
+pub fn all_transactions() -> FilterBox {
+    FilterBox::Pipeline(PipelineEventFilter::new().entity_kind(PipelineEntityKind::Transaction))
+}
+
+/// Whether a pipeline event reports a final status, committed or rejected.
+pub fn is_final(event: &PipelineEvent) -> bool {
+    matches!(
+        event.status,
+        PipelineStatus::Committed | PipelineStatus::Rejected(_)
+    )
+}
+
+/// How to reconnect when the event stream breaks.
+#[derive(Debug, Clone, Copy)]
+pub struct Reconnect {
+    /// Delay before the first reconnection attempt.
+    pub initial_delay: Duration,
+    /// Upper bound of the delay, which doubles after each failed attempt.
+    pub max_delay: Duration,
+    /// Give up after this many attempts in a row, `None` to retry forever.
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for Reconnect {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+            max_attempts: Some(10),
+        }
+    }
+}
+
+/// A subscription to the events matching a filter.
+#[derive(Debug, Clone)]
+pub struct EventListener {
+    client: Client,
+    filter: FilterBox,
+    reconnect: Reconnect,
+}
+
+impl EventListener {
+    /// Listen for the events matching `filter` with the default [`Reconnect`] policy.
+    pub fn new(client: Client, filter: FilterBox) -> Self {
+        Self {
+            client,
+            filter,
+            reconnect: Reconnect::default(),
+        }
+    }
+
+    /// Use another reconnection policy.
+    #[must_use]
+    pub fn reconnect(mut self, reconnect: Reconnect) -> Self {
+        self.reconnect = reconnect;
+        self
+    }
+
+    /// Pass every event to `callback` until it returns [`ControlFlow::Break`].
+    ///
+    /// Blocks the current thread. The attempt counter is reset by every
+    /// received event, so only consecutive failures count towards
+    /// [`Reconnect::max_attempts`].
+    ///
+    /// # Errors
+    /// Fails when the reconnection attempts are exhausted.
+    pub fn listen<B>(&self, callback: impl FnMut(Event) -> ControlFlow<B>) -> Result<B> {
+        runtime()?.block_on(self.drive(None, future::pending(), callback))
+    }
+
+    /// Receive the events as an asynchronous stream.
+    ///
+    /// The first subscription is made before returning, so no event that
+    /// happens afterwards is missed. It then runs on a separate thread,
+    /// which closes the connection and stops as soon as the stream is
+    /// dropped. When the reconnection attempts are exhausted, the stream
+    /// yields the error and ends.
+    ///
+    /// Must not be called from within an asynchronous runtime.
+    ///
+    /// # Errors
+    /// Fails if the first subscription fails.
+    pub fn into_stream(self) -> Result<impl Stream<Item = Result<Event>>> {
+        let runtime = runtime()?;
+        let events = runtime
+            .block_on(self.subscribe())
+            .wrap_err("Failed to subscribe to events")?;
+        let (sender, receiver) = mpsc::unbounded();
+        let (closed, dropped) = oneshot::channel();
+        thread::spawn(move || {
+            let outcome = runtime.block_on(self.drive(Some(events), dropped.map(drop), |event| {
+                if sender.unbounded_send(Ok(event)).is_err() {
+                    // The receiver is gone
+                    return ControlFlow::Break(());
+                }
+                ControlFlow::Continue(())
+            }));
+            if let Err(error) = outcome {
+                // Nobody to tell if the receiver is gone
+                let _ = sender.unbounded_send(Err(error));
+            }
+        });
+        Ok(EventStream {
+            receiver,
+            _closed: closed,
+        })
+    }
+
+    async fn subscribe(&self) -> Result<Events> {
+        let events = self
+            .client
+            .listen_for_events_async(self.filter.clone())
+            .await?;
+        Ok(Box::pin(events))
+    }
+
+    /// Receive the events through a channel, like [`Self::into_stream`]
+    /// but for synchronous code, e.g. to wait with a timeout.
+    ///
+    /// # Errors
+    /// Fails if the first subscription fails.
+    pub fn into_receiver(self) -> Result<Subscription> {
+        self.into_receiver_until(|_| false)
+    }
+
+    /// Like [`Self::into_receiver`], but stops listening after the first
+    /// event for which `last` returns `true`, once it is sent.
+    ///
+    /// Use it when the filter matches nothing after a certain event, e.g.
+    /// the final status of a transaction, so the thread and the connection
+    /// don't outlive their use.
+    ///
+    /// # Errors
+    /// Fails if the first subscription fails.
+    pub fn into_receiver_until(
+        self,
+        last: impl Fn(&Event) -> bool + Send + 'static,
+    ) -> Result<Subscription> {
+        let runtime = runtime()?;
+        let events = runtime
+            .block_on(self.subscribe())
+            .wrap_err("Failed to subscribe to events")?;
+        let (sender, receiver) = std_mpsc::channel();
+        let (closed, dropped) = oneshot::channel();
+        thread::spawn(move || {
+            let outcome = runtime.block_on(self.drive(Some(events), dropped.map(drop), |event| {
+                let is_last = last(&event);
+                if sender.send(Ok(event)).is_err() || is_last {
+                    // The receiver is gone, or expects nothing more
+                    return ControlFlow::Break(());
+                }
+                ControlFlow::Continue(())
+            }));
+            if let Err(error) = outcome {
+                // Nobody to tell if the receiver is gone
+                let _ = sender.send(Err(error));
+            }
+        });
+        Ok(Subscription {
+            receiver,
+            _closed: closed,
+        })
+    }
+
+    /// Feed `callback` from the `connected` stream, if any, and from new
+    /// subscriptions made whenever the current one breaks. `stopped` ends
+    /// the loop with its value as soon as it completes, dropping the current
+    /// subscription and with it the connection, even while waiting for an
+    /// event.
+    async fn drive<B>(
+        &self,
+        connected: Option<Events>,
+        stopped: impl Future<Output = B> + Unpin,
+        callback: impl FnMut(Event) -> ControlFlow<B>,
+    ) -> Result<B> {
+        let forward = Box::pin(self.forward(connected, callback));
+        match future::select(forward, stopped).await {
+            Either::Left((outcome, _)) => outcome,
+            Either::Right((value, _)) => Ok(value),
+        }
+    }
+
+    async fn forward<B>(
+        &self,
+        mut connected: Option<Events>,
+        mut callback: impl FnMut(Event) -> ControlFlow<B>,
+    ) -> Result<B> {
+        let mut attempts = 0_u32;
+        let mut delay = self.reconnect.initial_delay;
+        loop {
+            let events = match connected.take() {
+                Some(events) => Ok(events),
+                None => self.subscribe().await,
+            };
+            let error = match events {
+                Ok(mut events) => {
+                    let mut error = eyre::eyre!("The event stream was closed by the peer");
+                    while let Some(event) = events.next().await {
+                        match event {
+                            Ok(event) => {
+                                attempts = 0;
+                                delay = self.reconnect.initial_delay;
+                                if let ControlFlow::Break(value) = callback(event) {
+                                    return Ok(value);
+                                }
+                            }
+                            Err(stream_error) => {
+                                error = stream_error;
+                                break;
+                            }
+                        }
+                    }
+                    error
+                }
+                Err(error) => error,
+            };
+
+            attempts += 1;
+            if self
+                .reconnect
+                .max_attempts
+                .map_or(false, |max_attempts| attempts > max_attempts)
+            {
+                return Err(error).wrap_err(format!(
+                    "Gave up listening for events after {} attempt(s)",
+                    attempts - 1
+                ));
+            }
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(self.reconnect.max_delay);
+        }
+    }
+}
+
+/// A runtime for the connections of a single listener.
+fn runtime() -> Result<Runtime> {
+    Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .wrap_err("Failed to start a runtime for the event listener")
+}
+
+/// The stream returned by [`EventListener::into_stream`].
+struct EventStream {
+    receiver: mpsc::UnboundedReceiver<Result<Event>>,
+    // Dropping it stops the listening thread
+    _closed: oneshot::Sender<()>,
+}
+
+impl Stream for EventStream {
+    type Item = Result<Event>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_next_unpin(cx)
+    }
+}
+
+/// The receiving end of [`EventListener::into_receiver`].
+///
+/// Dereferences to the channel. The last item is the error that ended the
+/// subscription, if any. Dropping it closes the connection and stops the
+/// listening thread.
+#[derive(Debug)]
+pub struct Subscription {
+    receiver: std_mpsc::Receiver<Result<Event>>,
+    // Dropping it stops the listening thread
+    _closed: oneshot::Sender<()>,
+}
+
+impl Deref for Subscription {
+    type Target = std_mpsc::Receiver<Result<Event>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.receiver
+    }
+}
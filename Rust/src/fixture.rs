@@ -0,0 +1,454 @@
+//! Declarative ledger fixtures applied to a running network.
+//!
+//! A [`Fixture`] describes the ledger a test or a script expects: roles,
+//! domains with their asset definitions and accounts, the public keys,
+//! initial balances, roles and metadata of the accounts. It is read from
+//! a YAML or JSON file and applied with [`apply`], which submits the same
+//! `RegisterBox`, `MintBox` and `GrantBox` instructions one would write by
+//! hand. Unlike a genesis built with `RawGenesisBlockBuilder`, it works
+//! against a network that is already running.
+//!
+//! ```yaml
+//! roles:
+//!   - id: rose_courier
+//!     permissions:
+//!       - definition: CanTransferUserAsset
+//!         params: { asset_id: "rose##alice@wonderland" }
+//! domains:
+//!   - id: looking_glass
+//!     metadata: { motto: Curiouser and curiouser }
+//!     asset_definitions:
+//!       - { name: time, value_type: Fixed, mintable: Once }
+//!     accounts:
+//!       - name: white_rabbit
+//!         public_keys: [ed0120...]
+//!         balances: { time#looking_glass: 12.34 }
+//!         roles: [rose_courier]
+//! ```
+//!
+//! Applying a fixture is idempotent: existing entities are kept as they are
+//! (see [`crate::ensure`]), balances are only minted into accounts that don't
+//! hold the asset yet and roles are only granted to accounts that lack them.
+
+use std::{collections::BTreeMap, fmt, fs, path::Path};
+
+use eyre::{Result, WrapErr};
+use iroha_client::client::{Client, ClientQueryError};
+use iroha_data_model::{
+    metadata::{Limits as MetadataLimits, Metadata},
+    prelude::*,
+    query::{
+        asset::model::{FindAssetById, FindAssetDefinitionById},
+        error::{FindError, QueryExecutionFail},
+        role::model::{FindRoleByRoleId, FindRolesByAccountId},
+    },
+    ValidationFail,
+};
+use serde::Deserialize;
+
+use crate::ensure::{ensure_account, ensure_asset_definition, ensure_domain, Ensured};
+
+/// The desired state of a ledger.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Fixture {
+    /// Roles, registered before they are granted to the accounts.
+    #[serde(default)]
+    pub roles: Vec<RoleFixture>,
+    /// Domains with their contents.
+    #[serde(default)]
+    pub domains: Vec<DomainFixture>,
+}
+
+/// A role and its permission tokens.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RoleFixture {
+    /// Id of the role.
+    pub id: RoleId,
+    /// Permission tokens of the role.
+    #[serde(default)]
+    pub permissions: Vec<PermissionFixture>,
+}
+
+/// A permission token.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PermissionFixture {
+    /// Id of the token definition, such as `CanTransferUserAsset`.
+    pub definition: String,
+    /// Parameters of the token.
+    #[serde(default)]
+    pub params: serde_json::Value,
+}
+
+/// A domain, its asset definitions and its accounts.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DomainFixture {
+    /// Id of the domain.
+    pub id: DomainId,
+    /// Metadata of the domain.
+    #[serde(default)]
+    pub metadata: BTreeMap<Name, Scalar>,
+    /// Asset definitions registered in the domain.
+    #[serde(default)]
+    pub asset_definitions: Vec<AssetDefinitionFixture>,
+    /// Accounts registered in the domain.
+    #[serde(default)]
+    pub accounts: Vec<AccountFixture>,
+}
+
+/// An asset definition of the enclosing domain.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AssetDefinitionFixture {
+    /// Name of the asset, such as `time` for `time#looking_glass`.
+    pub name: Name,
+    /// Value type of the asset.
+    pub value_type: AssetValueType,
+    /// Whether the asset can be minted.
+    #[serde(default = "infinitely_mintable")]
+    pub mintable: Mintable,
+    /// Metadata of the definition.
+    #[serde(default)]
+    pub metadata: BTreeMap<Name, Scalar>,
+}
+
+/// An account of the enclosing domain.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AccountFixture {
+    /// Name of the account, such as `white_rabbit` for `white_rabbit@looking_glass`.
+    pub name: Name,
+    /// Public keys of the signatories.
+    pub public_keys: Vec<PublicKey>,
+    /// Metadata of the account.
+    #[serde(default)]
+    pub metadata: BTreeMap<Name, Scalar>,
+    /// Initial balances by asset definition id, which may be in another domain.
+    #[serde(default)]
+    pub balances: BTreeMap<AssetDefinitionId, Scalar>,
+    /// Roles granted to the account.
+    #[serde(default)]
+    pub roles: Vec<RoleId>,
+}
+
+/// A plain value of a metadata entry or a balance.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum Scalar {
+    /// `true` or `false`.
+    Bool(bool),
+    /// A whole number. Stored as `u32` in metadata if it fits.
+    Integer(u128),
+    /// A fractional number, stored as `Fixed`.
+    Decimal(f64),
+    /// A string.
+    String(String),
+}
+
+impl Scalar {
+    /// The value of a metadata entry.
+    ///
+    /// # Errors
+    /// Fails if a decimal isn't a valid `Fixed`.
+    pub fn to_metadata_value(&self) -> Result<Value> {
+        Ok(match self {
+            Self::Bool(value) => Value::Bool(*value),
+            Self::Integer(value) => match u32::try_from(*value) {
+                Ok(value) => value.to_value(),
+                Err(_) => value.to_value(),
+            },
+            Self::Decimal(value) => value.try_to_value()?,
+            Self::String(value) => Value::String(value.clone()),
+        })
+    }
+
+    /// The amount of an asset of `value_type`.
+    ///
+    /// # Errors
+    /// Fails if the value doesn't fit `value_type`.
+    pub fn to_asset_value(&self, value_type: AssetValueType) -> Result<AssetValue> {
+        Ok(match (value_type, self) {
+            (AssetValueType::Quantity, Self::Integer(value)) => AssetValue::Quantity(
+                u32::try_from(*value).wrap_err(format!("{value} is too large for a `Quantity`"))?,
+            ),
+            (AssetValueType::BigQuantity, Self::Integer(value)) => AssetValue::BigQuantity(*value),
+            (AssetValueType::Fixed, Self::Integer(value)) => {
+                AssetValue::Fixed((*value as f64).try_into()?)
+            }
+            (AssetValueType::Fixed, Self::Decimal(value)) => {
+                AssetValue::Fixed((*value).try_into()?)
+            }
+            (value_type, value) => {
+                return Err(eyre::eyre!(
+                    "{value:?} isn't an amount of a `{value_type:?}` asset"
+                ))
+            }
+        })
+    }
+
+    /// The amount of an asset of `value_type`, as an instruction argument.
+    ///
+    /// # Errors
+    /// Fails if the value doesn't fit `value_type`.
+    pub fn to_amount(&self, value_type: AssetValueType) -> Result<Value> {
+        Ok(match self.to_asset_value(value_type)? {
+            AssetValue::Quantity(value) => value.to_value(),
+            AssetValue::BigQuantity(value) => value.to_value(),
+            AssetValue::Fixed(value) => Value::Numeric(NumericValue::Fixed(value)),
+            AssetValue::Store(_) => unreachable!("Store assets have no amount"),
+        })
+    }
+}
+
+fn infinitely_mintable() -> Mintable {
+    Mintable::Infinitely
+}
+
+impl Fixture {
+    /// Read a fixture from a file, as JSON if it ends with `.json`
+    /// and as YAML otherwise.
+    ///
+    /// # Errors
+    /// Fails if the file can't be read or parsed.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .wrap_err(format!("Failed to read fixture `{}`", path.display()))?;
+        let fixture = if path.extension().map_or(false, |ext| ext == "json") {
+            Self::from_json(&contents)
+        } else {
+            Self::from_yaml(&contents)
+        };
+        fixture.wrap_err(format!("Invalid fixture `{}`", path.display()))
+    }
+
+    /// Parse a fixture from YAML.
+    ///
+    /// # Errors
+    /// Fails if the fixture is invalid.
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        Ok(serde_yaml::from_str(yaml)?)
+    }
+
+    /// Parse a fixture from JSON.
+    ///
+    /// # Errors
+    /// Fails if the fixture is invalid.
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+/// What [`apply`] did.
+#[derive(Debug, Clone, Default)]
+pub struct FixtureReport {
+    /// Domains and whether they were registered.
+    pub domains: Vec<(DomainId, Ensured)>,
+    /// Asset definitions and whether they were registered.
+    pub asset_definitions: Vec<(AssetDefinitionId, Ensured)>,
+    /// Accounts and whether they were registered.
+    pub accounts: Vec<(AccountId, Ensured)>,
+    /// Roles that were registered. The others existed already.
+    pub roles_registered: Vec<RoleId>,
+    /// Assets that got their initial balance.
+    pub minted: Vec<AssetId>,
+    /// Roles that were granted.
+    pub granted: Vec<(RoleId, AccountId)>,
+}
+
+impl FixtureReport {
+    /// Entities that exist but don't match the fixture.
+    pub fn differences(&self) -> impl Iterator<Item = (String, &Ensured)> {
+        let domains = self.domains.iter().map(|(id, e)| (id.to_string(), e));
+        let definitions = self
+            .asset_definitions
+            .iter()
+            .map(|(id, e)| (id.to_string(), e));
+        let accounts = self.accounts.iter().map(|(id, e)| (id.to_string(), e));
+        domains
+            .chain(definitions)
+            .chain(accounts)
+            .filter(|(_, ensured)| matches!(ensured, Ensured::Differs(_)))
+    }
+}
+
+impl fmt::Display for FixtureReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let registered = |entities: &[(_, Ensured)]| {
+            entities
+                .iter()
+                .filter(|(_, ensured)| ensured.is_registered())
+                .count()
+        };
+        writeln!(
+            f,
+            "Registered {} domain(s), {} asset definition(s), {} account(s) and {} role(s)",
+            registered(&self.domains),
+            registered(&self.asset_definitions),
+            registered(&self.accounts),
+            self.roles_registered.len(),
+        )?;
+        write!(
+            f,
+            "Minted {} balance(s), granted {} role(s)",
+            self.minted.len(),
+            self.granted.len()
+        )?;
+        for (id, ensured) in self.differences() {
+            write!(f, "\n`{id}` {ensured}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Bring the ledger to the state of `fixture`, signing as the client's account.
+///
+/// Domains, asset definitions, accounts and roles are registered one by one,
+/// then the balances and roles are submitted in a single transaction.
+///
+/// # Errors
+/// Fails if a query fails or an instruction is rejected.
+pub fn apply(client: &Client, fixture: &Fixture) -> Result<FixtureReport> {
+    let mut report = FixtureReport::default();
+    let mut value_types = BTreeMap::new();
+
+    for domain in &fixture.domains {
+        let new_domain = Domain::new(domain.id.clone()).with_metadata(metadata(&domain.metadata)?);
+        let ensured = ensure_domain(client, new_domain)?;
+        report.domains.push((domain.id.clone(), ensured));
+
+        for definition in &domain.asset_definitions {
+            let id = AssetDefinitionId::new(definition.name.clone(), domain.id.clone());
+            let mut new_definition = AssetDefinition::new(id.clone(), definition.value_type)
+                .with_metadata(metadata(&definition.metadata)?);
+            new_definition.mintable = definition.mintable;
+            let ensured = ensure_asset_definition(client, new_definition)?;
+            value_types.insert(id.clone(), definition.value_type);
+            report.asset_definitions.push((id, ensured));
+        }
+
+        for account in &domain.accounts {
+            let id = AccountId::new(account.name.clone(), domain.id.clone());
+            let new_account = Account::new(id.clone(), account.public_keys.clone())
+                .with_metadata(metadata(&account.metadata)?);
+            let ensured = ensure_account(client, new_account)?;
+            report.accounts.push((id, ensured));
+        }
+    }
+
+    for role in &fixture.roles {
+        if ensure_role(client, role)? {
+            report.roles_registered.push(role.id.clone());
+        }
+    }
+
+    let mut instructions: Vec<InstructionBox> = Vec::new();
+    for domain in &fixture.domains {
+        for account in &domain.accounts {
+            let account_id = AccountId::new(account.name.clone(), domain.id.clone());
+
+            for (definition_id, amount) in &account.balances {
+                let asset_id = AssetId::new(definition_id.clone(), account_id.clone());
+                if holds(client, &asset_id)? {
+                    continue;
+                }
+                let value_type = match value_types.get(definition_id) {
+                    Some(value_type) => *value_type,
+                    None => value_type_of(client, definition_id)?,
+                };
+                let amount = amount
+                    .to_amount(value_type)
+                    .wrap_err(format!("Invalid balance of `{asset_id}`"))?;
+                instructions.push(MintBox::new(amount, IdBox::AssetId(asset_id.clone())).into());
+                report.minted.push(asset_id);
+            }
+
+            if account.roles.is_empty() {
+                continue;
+            }
+            let roles: Vec<RoleId> = client
+                .request(FindRolesByAccountId {
+                    id: account_id.clone().into(),
+                })?
+                .collect::<Result<_, _>>()?;
+            for role_id in &account.roles {
+                if !roles.contains(role_id) {
+                    instructions.push(GrantBox::new(role_id.clone(), account_id.clone()).into());
+                    report.granted.push((role_id.clone(), account_id.clone()));
+                }
+            }
+        }
+    }
+    if !instructions.is_empty() {
+        client
+            .submit_all_blocking(instructions)
+            .wrap_err("Failed to mint the balances and grant the roles")?;
+    }
+
+    Ok(report)
+}
+
+fn metadata(entries: &BTreeMap<Name, Scalar>) -> Result<Metadata> {
+    // The peer checks its own limits
+    let limits = MetadataLimits::new(u32::MAX, u32::MAX);
+    let mut metadata = Metadata::new();
+    for (key, value) in entries {
+        metadata
+            .insert_with_limits(key.clone(), value.to_metadata_value()?, limits)
+            .wrap_err(format!("Invalid metadata entry `{key}`"))?;
+    }
+    Ok(metadata)
+}
+
+/// Register `role` unless it exists. Returns whether it was registered.
+fn ensure_role(client: &Client, role: &RoleFixture) -> Result<bool> {
+    match client.request(FindRoleByRoleId {
+        id: role.id.clone().into(),
+    }) {
+        Ok(_) => return Ok(false),
+        Err(ClientQueryError::Validation(ValidationFail::QueryFailed(
+            QueryExecutionFail::Find(FindError::Role(_)),
+        ))) => {}
+        Err(error) => return Err(error).wrap_err(format!("Failed to query role `{}`", role.id)),
+    }
+
+    let mut new_role = Role::new(role.id.clone());
+    for permission in &role.permissions {
+        let definition = permission.definition.parse().wrap_err(format!(
+            "Invalid permission token definition `{}`",
+            permission.definition
+        ))?;
+        new_role = new_role.add_permission(PermissionToken::new(definition, &permission.params));
+    }
+    let register: InstructionBox = RegisterBox::new(new_role).into();
+    client
+        .submit_blocking(register)
+        .wrap_err(format!("Failed to register role `{}`", role.id))?;
+    Ok(true)
+}
+
+/// Whether the account of `asset_id` holds the asset.
+fn holds(client: &Client, asset_id: &AssetId) -> Result<bool> {
+    match client.request(FindAssetById {
+        id: asset_id.clone().into(),
+    }) {
+        Ok(_) => Ok(true),
+        Err(ClientQueryError::Validation(ValidationFail::QueryFailed(
+            QueryExecutionFail::Find(FindError::Asset(_)),
+        ))) => Ok(false),
+        Err(error) => Err(error).wrap_err(format!("Failed to query asset `{asset_id}`")),
+    }
+}
+
+/// Value type of a definition that isn't in the fixture.
+fn value_type_of(client: &Client, id: &AssetDefinitionId) -> Result<AssetValueType> {
+    let definition = client
+        .request(FindAssetDefinitionById {
+            id: id.clone().into(),
+        })
+        .wrap_err(format!("Failed to query asset definition `{id}`"))?;
+    Ok(definition.value_type)
+}
@@ -0,0 +1,278 @@
+//! Reproducible genesis blocks of any size.
+//!
+//! A [`GenesisSpec`] describes the ledger to generate: `domains` domains
+//! named `wonderland-{i}`, each with `accounts_per_domain` accounts named
+//! `alice-{j}` and `assets_per_domain` asset definitions named `xor-{k}`.
+//! Every account can hold an initial balance of every asset of its domain
+//! and can be granted the same set of roles.
+
+use std::{
+    fs::File,
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+
+use eyre::{Result, WrapErr};
+use iroha::samples::construct_validator;
+use iroha_crypto::{KeyGenConfiguration, KeyPair};
+use iroha_data_model::prelude::*;
+use iroha_genesis::{GenesisTransaction, RawGenesisBlock, RawGenesisBlockBuilder, ValidatorMode};
+
+/// Where the public keys of the generated accounts come from.
+#[derive(Debug, Clone)]
+pub enum KeySource {
+    /// Every account has the same key.
+    Shared(PublicKey),
+    /// Every account has a key derived from the seed `{prefix}{account_id}`,
+    /// so the private keys can be derived again later.
+    Seeded(String),
+    /// Every account has a fresh random key. The private keys are lost.
+    Random,
+}
+
+impl KeySource {
+    /// The key pair of `account_id`, unless the source only knows public keys.
+    ///
+    /// # Errors
+    /// Fails if the key generation fails.
+    pub fn key_pair(&self, account_id: &AccountId) -> Result<Option<KeyPair>> {
+        Ok(match self {
+            Self::Shared(_) => None,
+            Self::Seeded(prefix) => {
+                let seed = format!("{prefix}{account_id}").into_bytes();
+                let configuration = KeyGenConfiguration::default().use_seed(seed);
+                Some(KeyPair::generate_with_configuration(configuration)?)
+            }
+            Self::Random => Some(KeyPair::generate()?),
+        })
+    }
+
+    /// The public key of `account_id`.
+    ///
+    /// # Errors
+    /// Fails if the key generation fails.
+    pub fn public_key(&self, account_id: &AccountId) -> Result<PublicKey> {
+        match self {
+            Self::Shared(public_key) => Ok(public_key.clone()),
+            _ => Ok(self
+                .key_pair(account_id)?
+                .expect("Only shared keys are unknown")
+                .public_key()
+                .clone()),
+        }
+    }
+}
+
+/// How the genesis refers to the validator.
+#[derive(Debug, Clone)]
+pub enum ValidatorSource {
+    /// By the path of the compiled validator, relative to the genesis file.
+    Path(PathBuf),
+    /// By building the validator crate at the path and inlining it.
+    /// Slow, but the genesis is then usable without the file.
+    Inline(PathBuf),
+}
+
+impl ValidatorSource {
+    /// The validator as it is written in the genesis.
+    ///
+    /// # Errors
+    /// Fails if the validator has to be built and the build fails.
+    pub fn mode(&self) -> Result<ValidatorMode> {
+        Ok(match self {
+            Self::Path(path) => ValidatorMode::Path(path.clone().into()),
+            Self::Inline(path) => ValidatorMode::Inline(construct_validator(path).wrap_err(
+                format!("Failed to build the validator at `{}`", path.display()),
+            )?),
+        })
+    }
+}
+
+/// A role registered in the genesis and granted to every generated account.
+#[derive(Debug, Clone)]
+pub struct RoleSpec {
+    /// Id of the role.
+    pub id: RoleId,
+    /// Permission tokens of the role.
+    pub permissions: Vec<PermissionToken>,
+}
+
+/// Parameters of a generated genesis.
+#[derive(Debug, Clone)]
+pub struct GenesisSpec {
+    /// Number of domains.
+    pub domains: u32,
+    /// Number of accounts in each domain.
+    pub accounts_per_domain: u32,
+    /// Number of asset definitions in each domain.
+    pub assets_per_domain: u32,
+    /// Value type of the asset definitions.
+    pub asset_value_type: AssetValueType,
+    /// Amount of each asset minted for each account of its domain.
+    /// Ignored for `Store` assets.
+    pub initial_balance: u32,
+    /// Public keys of the accounts.
+    pub keys: KeySource,
+    /// Roles granted to every account.
+    pub roles: Vec<RoleSpec>,
+    /// The validator.
+    pub validator: ValidatorSource,
+}
+
+impl GenesisSpec {
+    /// Id of the `i`-th domain.
+    pub fn domain_id(i: u32) -> DomainId {
+        format!("wonderland-{i}").parse().expect("Valid")
+    }
+
+    /// Id of the `j`-th account of `domain_id`.
+    pub fn account_id(domain_id: &DomainId, j: u32) -> AccountId {
+        AccountId::new(
+            format!("alice-{j}").parse().expect("Valid"),
+            domain_id.clone(),
+        )
+    }
+
+    /// Id of the `k`-th asset definition of `domain_id`.
+    pub fn asset_definition_id(domain_id: &DomainId, k: u32) -> AssetDefinitionId {
+        AssetDefinitionId::new(
+            format!("xor-{k}").parse().expect("Valid"),
+            domain_id.clone(),
+        )
+    }
+
+    /// The initial balance as an instruction argument.
+    ///
+    /// # Errors
+    /// Fails if it can't be represented as [`Self::asset_value_type`].
+    pub fn initial_value(&self) -> Result<Option<Value>> {
+        if self.initial_balance == 0 {
+            return Ok(None);
+        }
+        Ok(match self.asset_value_type {
+            AssetValueType::Quantity => Some(self.initial_balance.to_value()),
+            AssetValueType::BigQuantity => Some(u128::from(self.initial_balance).to_value()),
+            AssetValueType::Fixed => Some(f64::from(self.initial_balance).try_to_value()?),
+            AssetValueType::Store => None,
+        })
+    }
+
+    /// Pass the instructions registering the `i`-th domain, its accounts
+    /// and its asset definitions to `f`, one at a time.
+    ///
+    /// # Errors
+    /// Fails if a key can't be generated or `f` fails.
+    pub fn for_each_registration(
+        &self,
+        i: u32,
+        mut f: impl FnMut(InstructionBox) -> Result<()>,
+    ) -> Result<()> {
+        let domain_id = Self::domain_id(i);
+        f(RegisterBox::new(Domain::new(domain_id.clone())).into())?;
+        for j in 0..self.accounts_per_domain {
+            let account_id = Self::account_id(&domain_id, j);
+            let public_key = self.keys.public_key(&account_id)?;
+            f(RegisterBox::new(Account::new(account_id, [public_key])).into())?;
+        }
+        for k in 0..self.assets_per_domain {
+            let definition_id = Self::asset_definition_id(&domain_id, k);
+            f(RegisterBox::new(AssetDefinition::new(definition_id, self.asset_value_type)).into())?;
+        }
+        Ok(())
+    }
+
+    /// Pass the instructions registering and granting the roles and minting
+    /// the initial balances of the accounts of the `i`-th domain to `f`,
+    /// one at a time.
+    ///
+    /// The role registrations come with the first domain.
+    ///
+    /// # Errors
+    /// Fails if the initial balance is out of range or `f` fails.
+    pub fn for_each_extra(
+        &self,
+        i: u32,
+        mut f: impl FnMut(InstructionBox) -> Result<()>,
+    ) -> Result<()> {
+        if i == 0 {
+            for role in &self.roles {
+                let new_role = role
+                    .permissions
+                    .iter()
+                    .cloned()
+                    .fold(Role::new(role.id.clone()), NewRole::add_permission);
+                f(RegisterBox::new(new_role).into())?;
+            }
+        }
+
+        let initial_value = self.initial_value()?;
+        let domain_id = Self::domain_id(i);
+        for j in 0..self.accounts_per_domain {
+            let account_id = Self::account_id(&domain_id, j);
+            for role in &self.roles {
+                f(GrantBox::new(role.id.clone(), account_id.clone()).into())?;
+            }
+            if let Some(value) = &initial_value {
+                for k in 0..self.assets_per_domain {
+                    let asset_id =
+                        AssetId::new(Self::asset_definition_id(&domain_id, k), account_id.clone());
+                    f(MintBox::new(value.clone(), IdBox::AssetId(asset_id)).into())?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Build the genesis block.
+    ///
+    /// The whole block is kept in memory, see [`crate::genesis_writer`]
+    /// for large ledgers.
+    ///
+    /// # Errors
+    /// Fails if a key can't be generated or the validator can't be built.
+    pub fn build(&self) -> Result<RawGenesisBlock> {
+        let mut builder = RawGenesisBlockBuilder::new();
+        let mut extras = Vec::new();
+        for i in 0..self.domains {
+            let domain_id = Self::domain_id(i);
+            let mut domain = builder.domain(domain_id.clone());
+            for j in 0..self.accounts_per_domain {
+                let account_id = Self::account_id(&domain_id, j);
+                domain =
+                    domain.account(account_id.name.clone(), self.keys.public_key(&account_id)?);
+            }
+            for k in 0..self.assets_per_domain {
+                let definition_id = Self::asset_definition_id(&domain_id, k);
+                domain = domain.asset(definition_id.name.clone(), self.asset_value_type);
+            }
+            builder = domain.finish_domain();
+            self.for_each_extra(i, |instruction| {
+                extras.push(instruction);
+                Ok(())
+            })?;
+        }
+
+        let mut genesis = builder.validator(self.validator.mode()?).build();
+        if !extras.is_empty() {
+            // Roles and balances refer to the entities registered above,
+            // so they go into a transaction of their own
+            genesis.transactions.push(GenesisTransaction {
+                isi: extras.into_iter().collect(),
+            });
+        }
+        Ok(genesis)
+    }
+
+    /// Build the genesis block and write it to `path` as JSON.
+    ///
+    /// # Errors
+    /// Fails if the block can't be built or written.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<RawGenesisBlock> {
+        let path = path.as_ref();
+        let genesis = self.build()?;
+        let file = File::create(path).wrap_err(format!("Failed to create `{}`", path.display()))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), &genesis)
+            .wrap_err(format!("Failed to write `{}`", path.display()))?;
+        Ok(genesis)
+    }
+}
@@ -0,0 +1,156 @@
+//! Writing a genesis block without holding it in memory.
+//!
+//! [`GenesisWriter`] serializes the instructions to the output as they are
+//! pushed, so the memory use doesn't grow with the number of entities. The
+//! output is the same JSON as a serialized [`RawGenesisBlock`], which can be
+//! read back with [`RawGenesisBlock::from_path`] and passed to
+//! `GenesisNetwork::from_configuration`.
+//!
+//! [`RawGenesisBlock`]: iroha_genesis::RawGenesisBlock
+//! [`RawGenesisBlock::from_path`]: iroha_genesis::RawGenesisBlock::from_path
+
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use eyre::{Result, WrapErr};
+use iroha_data_model::prelude::*;
+use iroha_genesis::ValidatorMode;
+
+use crate::genesis::GenesisSpec;
+
+/// Streaming serializer of a genesis block.
+#[derive(Debug)]
+pub struct GenesisWriter<W: Write> {
+    out: W,
+    transactions: u64,
+    instructions: u64,
+    /// Whether a transaction is open and how many instructions it has.
+    open: Option<u64>,
+}
+
+impl GenesisWriter<BufWriter<File>> {
+    /// Write the genesis block to the file at `path`.
+    ///
+    /// # Errors
+    /// Fails if the file can't be created.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::create(path).wrap_err(format!("Failed to create `{}`", path.display()))?;
+        Self::new(BufWriter::new(file))
+    }
+}
+
+impl<W: Write> GenesisWriter<W> {
+    /// Write the genesis block to `out`.
+    ///
+    /// # Errors
+    /// Fails if the output fails.
+    pub fn new(mut out: W) -> Result<Self> {
+        out.write_all(br#"{"transactions":["#)?;
+        Ok(Self {
+            out,
+            transactions: 0,
+            instructions: 0,
+            open: None,
+        })
+    }
+
+    /// Start a new transaction. The instructions pushed before go into
+    /// the previous one.
+    ///
+    /// # Errors
+    /// Fails if the output fails.
+    pub fn begin_transaction(&mut self) -> Result<()> {
+        self.end_transaction()?;
+        if self.transactions > 0 {
+            self.out.write_all(b",")?;
+        }
+        self.out.write_all(br#"{"isi":["#)?;
+        self.transactions += 1;
+        self.open = Some(0);
+        Ok(())
+    }
+
+    /// Append `instruction` to the current transaction, starting one if needed.
+    ///
+    /// # Errors
+    /// Fails if the instruction can't be serialized or the output fails.
+    pub fn push(&mut self, instruction: &InstructionBox) -> Result<()> {
+        if self.open.is_none() {
+            self.begin_transaction()?;
+        }
+        let count = self.open.as_mut().expect("Opened above");
+        if *count > 0 {
+            self.out.write_all(b",")?;
+        }
+        serde_json::to_writer(&mut self.out, instruction)?;
+        *count += 1;
+        self.instructions += 1;
+        Ok(())
+    }
+
+    /// The number of instructions written so far.
+    pub fn instructions(&self) -> u64 {
+        self.instructions
+    }
+
+    /// Close the block with `validator` and flush the output.
+    ///
+    /// # Errors
+    /// Fails if the output fails.
+    pub fn finish(mut self, validator: &ValidatorMode) -> Result<W> {
+        self.end_transaction()?;
+        self.out.write_all(br#"],"validator":"#)?;
+        serde_json::to_writer(&mut self.out, validator)?;
+        self.out.write_all(b"}")?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    fn end_transaction(&mut self) -> Result<()> {
+        if self.open.take().is_some() {
+            self.out.write_all(b"]}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Write the genesis described by `spec` to `path` domain by domain.
+///
+/// The registrations go into the first transaction and the roles and
+/// balances into the second one, as with [`GenesisSpec::build`]. Returns
+/// the number of instructions written.
+///
+/// # Errors
+/// Fails if a key can't be generated, the validator can't be built or the
+/// file can't be written.
+pub fn write_genesis(spec: &GenesisSpec, path: impl AsRef<Path>) -> Result<u64> {
+    // Build the validator first, so a failure doesn't leave a huge file behind
+    let validator = spec.validator.mode()?;
+    let mut writer = GenesisWriter::create(path)?;
+
+    writer.begin_transaction()?;
+    for i in 0..spec.domains {
+        spec.for_each_registration(i, |instruction| writer.push(&instruction))?;
+    }
+
+    // The second pass generates the same ids again instead of keeping them.
+    // The transaction is only started if there is anything to put there.
+    let mut extras_started = false;
+    for i in 0..spec.domains {
+        spec.for_each_extra(i, |instruction| {
+            if !extras_started {
+                writer.begin_transaction()?;
+                extras_started = true;
+            }
+            writer.push(&instruction)
+        })?;
+    }
+
+    let instructions = writer.instructions();
+    writer.finish(&validator)?;
+    Ok(instructions)
+}
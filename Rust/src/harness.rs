@@ -0,0 +1,292 @@
+//! Common entry point of the examples.
+//!
+//! Every example that talks to a peer calls [`run`] from its `main`:
+//!
+//! ```no_run
+//! use std::process::ExitCode;
+//!
+//! use iroha_config::client::Configuration;
+//!
+//! fn main() -> ExitCode {
+//!     iroha_2_examples::harness::run("Domain registration", domain_registration_test)
+//! }
+//!
+//! fn domain_registration_test(config: &Configuration) -> eyre::Result<()> {
+//!     Ok(())
+//! }
+//! ```
+//!
+//! The configuration file is taken from the `--config <path>` flag, then from
+//! the `IROHA_CONFIG` environment variable, and falls back to `./config.json`.
+//! It is then layered with a profile and environment overrides, see [`crate::config`].
+//!
+//! Examples with options of their own parse them with [`Args`] and start
+//! through [`run_with_args`] instead.
+
+use std::{
+    error::Error,
+    ffi::OsString,
+    fmt,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    str::FromStr,
+};
+
+use eyre::WrapErr;
+
+use iroha_config::client::Configuration;
+
+use crate::config::{ConfigLoader, LoadedConfig, CONFIG_PATH_ENV};
+
+/// Configuration file used when neither the flag nor the variable is given.
+pub const DEFAULT_CONFIG_PATH: &str = "./config.json";
+/// Command line flag holding the path of the configuration file.
+pub const CONFIG_FLAG: &str = "--config";
+
+/// Exit code of an example that ran successfully.
+pub const EXIT_SUCCESS: u8 = 0;
+/// Exit code of an example that failed while running.
+pub const EXIT_EXAMPLE_FAILED: u8 = 1;
+/// Exit code of an example that couldn't be configured, because of its
+/// arguments or its configuration file.
+pub const EXIT_CONFIG_FAILED: u8 = 2;
+
+/// Errors of setting up an example.
+#[derive(Debug)]
+pub enum HarnessError {
+    /// `--config` was passed without a path.
+    MissingFlagValue,
+    /// The configuration at `path` couldn't be loaded.
+    Config {
+        /// Path of the base configuration file.
+        path: PathBuf,
+        /// Underlying error.
+        source: eyre::Report,
+    },
+}
+
+impl fmt::Display for HarnessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingFlagValue => write!(f, "`{CONFIG_FLAG}` expects a path"),
+            Self::Config { path, .. } => {
+                write!(
+                    f,
+                    "failed to load the configuration from `{}`",
+                    path.display()
+                )
+            }
+        }
+    }
+}
+
+impl Error for HarnessError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::MissingFlagValue => None,
+            Self::Config { source, .. } => Some(source.as_ref()),
+        }
+    }
+}
+
+/// Resolve the configuration file path of the current process.
+///
+/// # Errors
+/// Fails if `--config` is the last argument.
+pub fn config_path() -> Result<PathBuf, HarnessError> {
+    config_path_from(
+        std::env::args_os().skip(1),
+        std::env::var_os(CONFIG_PATH_ENV),
+    )
+}
+
+/// Resolve the configuration file path from the given arguments (without
+/// the program name) and the value of `IROHA_CONFIG`.
+///
+/// # Errors
+/// Fails if `--config` is the last argument.
+pub fn config_path_from(
+    args: impl IntoIterator<Item = OsString>,
+    env: Option<OsString>,
+) -> Result<PathBuf, HarnessError> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == CONFIG_FLAG {
+            return args
+                .next()
+                .map(PathBuf::from)
+                .ok_or(HarnessError::MissingFlagValue);
+        }
+        if let Some(path) = arg
+            .to_str()
+            .and_then(|arg| arg.strip_prefix(CONFIG_FLAG)?.strip_prefix('='))
+        {
+            return Ok(path.into());
+        }
+    }
+    Ok(env.map_or_else(|| DEFAULT_CONFIG_PATH.into(), PathBuf::from))
+}
+
+/// Load the layered configuration from `path`.
+///
+/// # Errors
+/// Fails if the configuration can't be read, merged or built.
+pub fn load_configuration(path: impl AsRef<Path>) -> Result<LoadedConfig, HarnessError> {
+    let path = path.as_ref();
+    ConfigLoader::new(path)
+        .profile_from_env()
+        .load()
+        .map_err(|source| HarnessError::Config {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+/// Run an example against the configuration of the current process.
+///
+/// Prints the outcome and returns [`EXIT_SUCCESS`], [`EXIT_EXAMPLE_FAILED`]
+/// or [`EXIT_CONFIG_FAILED`].
+pub fn run<F>(name: &str, example: F) -> ExitCode
+where
+    F: FnOnce(&Configuration) -> eyre::Result<()>,
+{
+    run_with_args(name, |_| Ok(()), |config, ()| example(config))
+}
+
+/// Like [`run`], for an example with command line options.
+///
+/// `parse` gets the arguments without the program name, `--config`
+/// included. If it fails, the example doesn't run and the exit code is
+/// [`EXIT_CONFIG_FAILED`].
+pub fn run_with_args<O, P, F>(name: &str, parse: P, example: F) -> ExitCode
+where
+    P: FnOnce(Args) -> eyre::Result<O>,
+    F: FnOnce(&Configuration, &O) -> eyre::Result<()>,
+{
+    let options = match parse(Args::from_env()) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{name} example couldn't start: {error:?}");
+            return ExitCode::from(EXIT_CONFIG_FAILED);
+        }
+    };
+    let loaded = match config_path().and_then(load_configuration) {
+        Ok(loaded) => loaded,
+        Err(error) => {
+            eprintln!(
+                "{name} example couldn't start: {:?}",
+                eyre::Report::new(error)
+            );
+            return ExitCode::from(EXIT_CONFIG_FAILED);
+        }
+    };
+
+    match example(&loaded.config, &options) {
+        Ok(()) => {
+            println!("{name} example works!");
+            ExitCode::from(EXIT_SUCCESS)
+        }
+        Err(error) => {
+            eprintln!("{name} example failed: {error:?}");
+            ExitCode::from(EXIT_EXAMPLE_FAILED)
+        }
+    }
+}
+
+/// Command line arguments of an example, read flag by flag.
+///
+/// Values are taken both from `--flag value` and from `--flag=value`.
+/// `--config` and its value are skipped, since [`run_with_args`] reads them.
+///
+/// ```
+/// use iroha_2_examples::harness::Args;
+///
+/// let mut args = Args::new(["--count=3", "--config", "peer.json", "--resume"].map(String::from));
+/// let (mut count, mut resume) = (1_u32, false);
+/// while let Some(flag) = args.next_flag()? {
+///     match flag.as_str() {
+///         "--count" => count = args.parse()?,
+///         "--resume" => resume = true,
+///         _ => return Err(Args::unexpected(&flag)),
+///     }
+/// }
+/// assert_eq!((count, resume), (3, true));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+#[derive(Debug)]
+pub struct Args {
+    args: std::vec::IntoIter<String>,
+    // The last flag, and its value if it was given as `--flag=value`
+    flag: String,
+    inline: Option<String>,
+}
+
+impl Args {
+    /// Read `args`, without the program name.
+    pub fn new(args: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            args: args.into_iter().collect::<Vec<_>>().into_iter(),
+            flag: String::new(),
+            inline: None,
+        }
+    }
+
+    /// Read the arguments of the current process.
+    pub fn from_env() -> Self {
+        Self::new(std::env::args().skip(1))
+    }
+
+    /// The next flag, or `None` after the last one.
+    ///
+    /// # Errors
+    /// Fails if the previous flag was given a value it didn't take.
+    pub fn next_flag(&mut self) -> eyre::Result<Option<String>> {
+        if self.inline.take().is_some() {
+            return Err(eyre::eyre!("`{}` doesn't take a value", self.flag));
+        }
+        while let Some(arg) = self.args.next() {
+            self.flag = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    self.inline = Some(value.to_owned());
+                    flag.to_owned()
+                }
+                _ => arg,
+            };
+            if self.flag == CONFIG_FLAG {
+                self.value()?;
+                continue;
+            }
+            return Ok(Some(self.flag.clone()));
+        }
+        Ok(None)
+    }
+
+    /// The value of the flag [`Self::next_flag`] just returned.
+    ///
+    /// # Errors
+    /// Fails if there is no value.
+    pub fn value(&mut self) -> eyre::Result<String> {
+        self.inline
+            .take()
+            .or_else(|| self.args.next())
+            .ok_or_else(|| eyre::eyre!("`{}` expects a value", self.flag))
+    }
+
+    /// The value of the flag [`Self::next_flag`] just returned, parsed.
+    ///
+    /// # Errors
+    /// Fails if there is no value or it can't be parsed.
+    pub fn parse<T>(&mut self) -> eyre::Result<T>
+    where
+        T: FromStr,
+        T::Err: Error + Send + Sync + 'static,
+    {
+        let value = self.value()?;
+        value.parse().wrap_err(format!("Invalid `{}`", self.flag))
+    }
+
+    /// The error for a flag the example doesn't know.
+    pub fn unexpected(flag: &str) -> eyre::Report {
+        eyre::eyre!("Unexpected argument `{flag}`")
+    }
+}
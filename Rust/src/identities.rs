@@ -0,0 +1,107 @@
+//! Acting as several accounts from one program.
+//!
+//! A client signs every transaction and query with the account and key pair
+//! of its configuration. [`Identities`] keeps the key pairs of several
+//! accounts and builds a client signing as any of them, sharing the rest of
+//! the configuration, so a single program can play every party of a scenario.
+
+use std::{collections::BTreeMap, fmt};
+
+use eyre::{Result, WrapErr};
+use iroha_client::client::Client;
+use iroha_config::client::Configuration;
+use iroha_crypto::KeyPair;
+use iroha_data_model::prelude::*;
+
+use crate::keys::{signing_as, KeyStore};
+
+/// No key pair is known for the account.
+#[derive(Debug, Clone)]
+pub struct UnknownIdentity(pub AccountId);
+
+impl fmt::Display for UnknownIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "No key pair is known for `{}`", self.0)
+    }
+}
+
+impl std::error::Error for UnknownIdentity {}
+
+/// Key pairs of several accounts on the same network.
+#[derive(Debug, Clone)]
+pub struct Identities {
+    config: Configuration,
+    key_pairs: BTreeMap<AccountId, KeyPair>,
+}
+
+impl Identities {
+    /// Start with the account of `config`, which is also used for
+    /// everything but the account and keys of the other identities.
+    ///
+    /// # Errors
+    /// Fails if the keys of `config` don't form a key pair.
+    pub fn new(config: &Configuration) -> Result<Self> {
+        let key_pair = KeyPair::new(config.public_key.clone(), config.private_key.clone())
+            .wrap_err(format!("Invalid key pair of `{}`", config.account_id))?;
+        let mut key_pairs = BTreeMap::new();
+        key_pairs.insert(config.account_id.clone(), key_pair);
+        Ok(Self {
+            config: config.clone(),
+            key_pairs,
+        })
+    }
+
+    /// Act as `account_id` with `key_pair`, replacing the previous key pair.
+    pub fn add(&mut self, account_id: AccountId, key_pair: KeyPair) -> &mut Self {
+        self.key_pairs.insert(account_id, key_pair);
+        self
+    }
+
+    /// Act as `account_id` with the key pair stored under its id in `store`.
+    ///
+    /// # Errors
+    /// Fails if the key pair can't be loaded.
+    pub fn add_from_store(&mut self, store: &KeyStore, account_id: AccountId) -> Result<&mut Self> {
+        let key_pair = store.load(&account_id.to_string())?;
+        Ok(self.add(account_id, key_pair))
+    }
+
+    /// The account of the configuration.
+    pub fn default_account(&self) -> &AccountId {
+        &self.config.account_id
+    }
+
+    /// The accounts with a known key pair.
+    pub fn accounts(&self) -> impl Iterator<Item = &AccountId> {
+        self.key_pairs.keys()
+    }
+
+    /// The key pair of `account_id`.
+    ///
+    /// # Errors
+    /// Fails with [`UnknownIdentity`] if it isn't known.
+    pub fn key_pair(&self, account_id: &AccountId) -> Result<&KeyPair> {
+        self.key_pairs
+            .get(account_id)
+            .ok_or_else(|| UnknownIdentity(account_id.clone()).into())
+    }
+
+    /// The configuration of a client signing as `account_id`.
+    ///
+    /// # Errors
+    /// Fails with [`UnknownIdentity`] if the key pair of the account isn't known.
+    pub fn config(&self, account_id: &AccountId) -> Result<Configuration> {
+        let key_pair = self.key_pair(account_id)?.clone();
+        Ok(signing_as(&self.config, account_id.clone(), key_pair))
+    }
+
+    /// A client signing as `account_id`.
+    ///
+    /// # Errors
+    /// Fails with [`UnknownIdentity`] if the key pair of the account isn't
+    /// known, and if the client can't be built.
+    pub fn client(&self, account_id: &AccountId) -> Result<Client> {
+        Client::new(&self.config(account_id)?)
+            .wrap_err(format!("Failed to build a client for `{account_id}`"))
+    }
+}
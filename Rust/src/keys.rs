@@ -0,0 +1,257 @@
+//! Generating, storing and loading the key pairs of accounts.
+//!
+//! A [`KeyStore`] is a directory with one JSON file per key pair, named
+//! after an alias such as the account id: `keys/white_rabbit@looking_glass.json`.
+//! The files have the same layout as the keys in `config.json`:
+//!
+//! ```json
+//! {
+//!   "public_key": "ed0120...",
+//!   "private_key": { "digest_function": "ed25519", "payload": "..." }
+//! }
+//! ```
+//!
+//! On Unix, the directory is only accessible to its owner and the files are
+//! only readable by their owner. Loading a key that others can read fails,
+//! like `ssh` refuses such keys.
+
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use eyre::{Result, WrapErr};
+use iroha_config::client::Configuration;
+use iroha_crypto::{Algorithm, KeyGenConfiguration, KeyPair};
+use iroha_data_model::prelude::*;
+
+/// Environment variable with the directory of the key store.
+pub const KEY_STORE_ENV: &str = "IROHA_KEY_STORE";
+/// Directory of the key store unless [`KEY_STORE_ENV`] is set.
+pub const DEFAULT_KEY_STORE: &str = "./keys";
+
+/// Generate a random key pair for `algorithm`.
+///
+/// # Errors
+/// Fails if the key generation fails.
+pub fn generate(algorithm: Algorithm) -> Result<KeyPair> {
+    let configuration = KeyGenConfiguration::default().with_algorithm(algorithm);
+    KeyPair::generate_with_configuration(configuration)
+        .wrap_err(format!("Failed to generate a {algorithm} key pair"))
+}
+
+/// Seed of the white rabbit's key pair in the tutorials.
+///
+/// The seed is public, so is the private key derived from it. See [`from_seed`].
+pub const WHITE_RABBIT_SEED: &[u8] = b"white_rabbit";
+
+/// Derive a key pair from `seed`, like `kagami crypto --seed`.
+///
+/// **Anyone who knows the seed can derive the private key.** The tutorials
+/// use a seed written in their code, so that later tutorials can sign as the
+/// account an earlier one registered. Never do this for a real account:
+/// [`generate`] its key pair and keep it in a [`KeyStore`] instead.
+///
+/// # Errors
+/// Fails if the key generation fails.
+pub fn from_seed(seed: &[u8]) -> Result<KeyPair> {
+    let configuration = KeyGenConfiguration::default().use_seed(seed.to_vec());
+    KeyPair::generate_with_configuration(configuration)
+        .wrap_err("Failed to derive a key pair from the seed")
+}
+
+/// `config` with the account and keys replaced, so a client built from it
+/// signs as `account_id`.
+pub fn signing_as(
+    config: &Configuration,
+    account_id: AccountId,
+    key_pair: KeyPair,
+) -> Configuration {
+    let (public_key, private_key) = key_pair.into();
+    let mut config = config.clone();
+    config.account_id = account_id;
+    config.public_key = public_key;
+    config.private_key = private_key;
+    config
+}
+
+/// A directory of key pairs, each stored under an alias.
+#[derive(Debug, Clone)]
+pub struct KeyStore {
+    dir: PathBuf,
+}
+
+impl KeyStore {
+    /// Open the key store in `dir`, creating the directory if needed.
+    ///
+    /// # Errors
+    /// Fails if the directory can't be created.
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        let mut builder = fs::DirBuilder::new();
+        builder.recursive(true);
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+        builder
+            .create(&dir)
+            .wrap_err(format!("Failed to create key store `{}`", dir.display()))?;
+        Ok(Self { dir })
+    }
+
+    /// Open the key store in `IROHA_KEY_STORE`, or in `./keys` if it isn't set.
+    ///
+    /// # Errors
+    /// Fails if the directory can't be created.
+    pub fn from_env() -> Result<Self> {
+        let dir =
+            std::env::var_os(KEY_STORE_ENV).map_or_else(|| DEFAULT_KEY_STORE.into(), PathBuf::from);
+        Self::open(dir)
+    }
+
+    /// Directory of the key store.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// File of the key pair stored under `alias`.
+    ///
+    /// # Errors
+    /// Fails if `alias` can't be used as a file name.
+    pub fn path(&self, alias: &str) -> Result<PathBuf> {
+        let valid = !alias.is_empty()
+            && !alias.starts_with('.')
+            && alias
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "_-.@#".contains(c));
+        if !valid {
+            return Err(eyre::eyre!("`{alias}` can't be used as a key alias"));
+        }
+        Ok(self.dir.join(format!("{alias}.json")))
+    }
+
+    /// Whether a key pair is stored under `alias`.
+    ///
+    /// # Errors
+    /// Fails if `alias` is invalid.
+    pub fn contains(&self, alias: &str) -> Result<bool> {
+        Ok(self.path(alias)?.exists())
+    }
+
+    /// Aliases of all the stored key pairs, sorted.
+    ///
+    /// # Errors
+    /// Fails if the directory can't be read.
+    pub fn aliases(&self) -> Result<Vec<String>> {
+        let mut aliases = Vec::new();
+        for entry in fs::read_dir(&self.dir)
+            .wrap_err(format!("Failed to read key store `{}`", self.dir.display()))?
+        {
+            let path = entry?.path();
+            if path.extension().map_or(false, |ext| ext == "json") {
+                if let Some(alias) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    aliases.push(alias.to_owned());
+                }
+            }
+        }
+        aliases.sort();
+        Ok(aliases)
+    }
+
+    /// Store `key_pair` under `alias`. Never overwrites a stored key pair,
+    /// since its private key would be lost.
+    ///
+    /// # Errors
+    /// Fails if a key pair is already stored under `alias` or the file
+    /// can't be written.
+    pub fn save(&self, alias: &str, key_pair: &KeyPair) -> Result<PathBuf> {
+        let path = self.path(alias)?;
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(&path).map_err(|error| {
+            let reason = if error.kind() == io::ErrorKind::AlreadyExists {
+                format!("A key pair is already stored as `{alias}`")
+            } else {
+                format!("Failed to create `{}`", path.display())
+            };
+            eyre::Report::new(error).wrap_err(reason)
+        })?;
+        serde_json::to_writer_pretty(&mut file, key_pair)?;
+        file.write_all(b"\n")?;
+        file.sync_all()?;
+        Ok(path)
+    }
+
+    /// Load the key pair stored under `alias`.
+    ///
+    /// # Errors
+    /// Fails if there is no such key pair, others can read its file
+    /// or the file is invalid.
+    pub fn load(&self, alias: &str) -> Result<KeyPair> {
+        let path = self.path(alias)?;
+        let contents =
+            fs::read_to_string(&path).wrap_err(format!("Failed to read key pair `{alias}`"))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mode = fs::metadata(&path)?.permissions().mode();
+            if mode & 0o077 != 0 {
+                return Err(eyre::eyre!(
+                    "`{}` is accessible to others (mode {:o}), run `chmod 600` on it",
+                    path.display(),
+                    mode & 0o777
+                ));
+            }
+        }
+        serde_json::from_str(&contents).wrap_err(format!("Invalid key pair `{}`", path.display()))
+    }
+
+    /// Generate a key pair for `algorithm` and store it under `alias`.
+    ///
+    /// # Errors
+    /// Fails if a key pair is already stored under `alias` or it can't be saved.
+    pub fn generate(&self, alias: &str, algorithm: Algorithm) -> Result<KeyPair> {
+        let key_pair = generate(algorithm)?;
+        self.save(alias, &key_pair)?;
+        Ok(key_pair)
+    }
+
+    /// Load the key pair stored under `alias`, generating an ed25519 one
+    /// if there is none.
+    ///
+    /// # Errors
+    /// Fails if the key pair can't be loaded or saved.
+    pub fn load_or_generate(&self, alias: &str) -> Result<KeyPair> {
+        if self.contains(alias)? {
+            self.load(alias)
+        } else {
+            self.generate(alias, Algorithm::Ed25519)
+        }
+    }
+
+    /// The public key stored under `alias` as a multihash, the format of
+    /// `PUBLIC_KEY` in `config.json`, e.g. `ed0120...`.
+    ///
+    /// # Errors
+    /// Fails if the key pair can't be loaded.
+    pub fn export_public_key(&self, alias: &str) -> Result<String> {
+        Ok(self.load(alias)?.public_key().to_string())
+    }
+
+    /// `config` signing as `account_id` with the key pair stored under the
+    /// account id.
+    ///
+    /// # Errors
+    /// Fails if the key pair can't be loaded.
+    pub fn signing_as(
+        &self,
+        config: &Configuration,
+        account_id: AccountId,
+    ) -> Result<Configuration> {
+        let key_pair = self.load(&account_id.to_string())?;
+        Ok(signing_as(config, account_id, key_pair))
+    }
+}
@@ -1,13 +1,37 @@
 use eyre::Result;
 use iroha_client::client::Client;
-use iroha_config::client::ConfigurationProxy;
-use iroha_config_base::proxy::LoadFromDisk;
 use std::path::Path;
 
-// FIXME: this should be a part of the client out of the box
+pub mod archive;
+pub mod batch;
+pub mod config;
+pub mod drift;
+pub mod ensure;
+pub mod events;
+pub mod fixture;
+pub mod genesis;
+pub mod genesis_writer;
+pub mod harness;
+pub mod identities;
+pub mod keys;
+pub mod load;
+pub mod local_network;
+pub mod metadata;
+pub mod multisig;
+pub mod query;
+pub mod rejection;
+pub mod status;
+pub mod store;
+pub mod transfer;
+pub mod triggers;
+pub mod wasm;
+
+pub use config::{ConfigLoader, Layer, LoadedConfig};
+
+/// Load a client from the configuration file at `path`.
+///
+/// The file is merged with the profile file from `IROHA_PROFILE`, if any,
+/// and with the `IROHA_*` environment variable overrides, see [`config`].
 pub fn load_client(path: impl AsRef<Path>) -> Result<Client> {
-    let mut config = ConfigurationProxy::from_path(path.as_ref().clone());
-    config.finish()?;
-    let config = config.build()?;
-    Client::new(&config)
+    ConfigLoader::new(path).profile_from_env().load()?.client()
 }
@@ -0,0 +1,338 @@
+//! Load generation with a bounded number of transactions in flight.
+//!
+//! [`LoadGenerator`] submits one transaction per job, keeping at most
+//! [`LoadConfig::max_in_flight`] of them unfinished. A transaction finishes
+//! when the peer reports it committed or rejected, or when it has been
+//! waiting longer than [`LoadConfig::status_timeout`]. Rejected transactions
+//! are signed again and resubmitted with a backoff until
+//! [`LoadConfig::max_retries`] is exhausted. Submissions the peer refuses,
+//! e.g. because its queue is full, are tried again the same way until
+//! [`LoadConfig::max_refusals`] is exhausted, and the job then fails.
+//!
+//! Transactions that time out are not resubmitted: they may still be in the
+//! queue of the peer and be committed later, so a resubmission could run the
+//! job twice. They are counted as timed out instead.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    sync::mpsc::RecvTimeoutError,
+    time::{Duration, Instant},
+};
+
+use eyre::{Result, WrapErr};
+use iroha_client::client::Client;
+use iroha_config::client::Configuration;
+use iroha_crypto::Hash;
+use iroha_data_model::{metadata::UnlimitedMetadata, prelude::*};
+
+use crate::{
+    events::{all_transactions, EventListener},
+    rejection::{decode, Rejection},
+};
+
+/// Parameters of a [`LoadGenerator`].
+#[derive(Debug, Clone, Copy)]
+pub struct LoadConfig {
+    /// The most transactions submitted but not yet finished.
+    pub max_in_flight: usize,
+    /// How many times a rejected transaction is resubmitted.
+    pub max_retries: u32,
+    /// How many times in a row the peer may refuse the submission of a job
+    /// before the job fails.
+    pub max_refusals: u32,
+    /// Delay before the first resubmission of a job.
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay, which doubles after each resubmission.
+    pub max_backoff: Duration,
+    /// How long to wait for the status of a transaction, `None` for the
+    /// `TRANSACTION_STATUS_TIMEOUT_MS` of the client configuration.
+    pub status_timeout: Option<Duration>,
+}
+
+impl Default for LoadConfig {
+    fn default() -> Self {
+        Self {
+            max_in_flight: 64,
+            max_retries: 3,
+            max_refusals: 10,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+            status_timeout: None,
+        }
+    }
+}
+
+/// Outcome of a load run.
+#[derive(Debug, Clone, Default)]
+pub struct LoadReport {
+    /// Transactions accepted for submission by the peer, resubmissions included.
+    pub submitted: u64,
+    /// Jobs whose transaction was committed.
+    pub committed: u64,
+    /// Jobs whose last transaction was rejected.
+    pub rejected: u64,
+    /// Jobs whose transaction got no status in time. It may still be
+    /// committed after the run.
+    pub timed_out: u64,
+    /// Jobs whose submissions the peer kept refusing.
+    pub failed: u64,
+    /// Resubmissions of rejected transactions.
+    pub retried: u64,
+    /// Submissions refused by the peer, including the last ones of failed jobs.
+    pub refused: u64,
+    /// Time from the first submission to the last final status.
+    pub elapsed: Duration,
+    /// Submission-to-commit latencies of the committed transactions, sorted.
+    pub latencies: Vec<Duration>,
+    /// Why the last transaction of each rejected job was rejected.
+    pub rejections: Vec<(Hash, Rejection)>,
+    /// Why the last submission of each failed job was refused.
+    pub failures: Vec<String>,
+}
+
+impl LoadReport {
+    /// Committed transactions per second.
+    pub fn tps(&self) -> f64 {
+        if self.elapsed.is_zero() {
+            return 0.0;
+        }
+        self.committed as f64 / self.elapsed.as_secs_f64()
+    }
+
+    /// The latency under which `percentile` percent of commits happened.
+    pub fn latency_percentile(&self, percentile: f64) -> Option<Duration> {
+        if self.latencies.is_empty() {
+            return None;
+        }
+        let rank = (percentile / 100.0 * self.latencies.len() as f64).ceil() as usize;
+        Some(self.latencies[rank.clamp(1, self.latencies.len()) - 1])
+    }
+}
+
+impl fmt::Display for LoadReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "submitted {}, committed {}, rejected {}, timed out {}, failed {}, retried {}, refused {}",
+            self.submitted,
+            self.committed,
+            self.rejected,
+            self.timed_out,
+            self.failed,
+            self.retried,
+            self.refused
+        )?;
+        write!(f, "{:.1} TPS over {:?}", self.tps(), self.elapsed)?;
+        for percentile in [50.0, 90.0, 99.0] {
+            if let Some(latency) = self.latency_percentile(percentile) {
+                write!(f, ", p{percentile} {latency:?}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A job waiting to be (re)submitted.
+struct Pending {
+    instructions: Vec<InstructionBox>,
+    /// Resubmissions so far.
+    retries: u32,
+    /// Refused submissions since the last accepted one.
+    refusals: u32,
+    backoff: Duration,
+    not_before: Instant,
+}
+
+/// Queue `job`, keeping the queue ordered by the time the jobs are due.
+fn schedule(retry_queue: &mut VecDeque<Pending>, job: Pending) {
+    let at = retry_queue.partition_point(|queued| queued.not_before <= job.not_before);
+    retry_queue.insert(at, job);
+}
+
+/// A submitted transaction waiting for its status.
+struct InFlight {
+    job: Pending,
+    submitted_at: Instant,
+}
+
+/// Submits jobs and tracks their transactions to the end.
+#[derive(Debug, Clone)]
+pub struct LoadGenerator {
+    client: Client,
+    config: LoadConfig,
+    status_timeout: Duration,
+}
+
+impl LoadGenerator {
+    /// Submit with a client of `client_config`, signing as its account.
+    ///
+    /// # Errors
+    /// Fails if the client can't be created.
+    pub fn new(client_config: &Configuration, config: LoadConfig) -> Result<Self> {
+        let status_timeout = config
+            .status_timeout
+            .unwrap_or_else(|| Duration::from_millis(client_config.transaction_status_timeout_ms));
+        Ok(Self {
+            client: Client::new(client_config)?,
+            config,
+            status_timeout,
+        })
+    }
+
+    /// Submit a transaction with the instructions of each job and return
+    /// once every job has a final outcome.
+    ///
+    /// # Errors
+    /// Fails if the status subscription fails or a transaction can't be built.
+    pub fn run(&self, jobs: impl IntoIterator<Item = Vec<InstructionBox>>) -> Result<LoadReport> {
+        // Subscribe before submitting, so no status is missed
+        let statuses =
+            EventListener::new(self.client.clone(), all_transactions()).into_receiver()?;
+
+        let mut jobs = jobs.into_iter().map(|instructions| Pending {
+            instructions,
+            retries: 0,
+            refusals: 0,
+            backoff: self.config.initial_backoff,
+            not_before: Instant::now(),
+        });
+        let mut retry_queue: VecDeque<Pending> = VecDeque::new();
+        let mut in_flight: HashMap<Hash, InFlight> = HashMap::new();
+        let mut report = LoadReport::default();
+        let started = Instant::now();
+
+        loop {
+            // Fill up the in-flight window, retries first
+            while in_flight.len() < self.config.max_in_flight {
+                let job = match retry_queue.front() {
+                    Some(job) if job.not_before <= Instant::now() => retry_queue.pop_front(),
+                    _ => jobs.next(),
+                };
+                let Some(job) = job else { break };
+                self.submit(job, &mut in_flight, &mut retry_queue, &mut report)?;
+            }
+
+            if in_flight.is_empty() && retry_queue.is_empty() {
+                break;
+            }
+
+            // Wait for a status, but not past the nearest deadline.
+            // A due retry only counts once there is room for it.
+            let now = Instant::now();
+            let next_retry = retry_queue
+                .front()
+                .filter(|_| in_flight.len() < self.config.max_in_flight)
+                .map(|job| job.not_before);
+            let deadline = in_flight
+                .values()
+                .map(|transaction| transaction.submitted_at + self.status_timeout)
+                .chain(next_retry)
+                .min()
+                .unwrap_or(now);
+            match statuses.recv_timeout(deadline.saturating_duration_since(now)) {
+                Ok(Ok(Event::Pipeline(event))) => {
+                    let Some(transaction) = in_flight.remove(&event.hash) else {
+                        // Not ours, or a status we don't wait for
+                        continue;
+                    };
+                    match event.status {
+                        PipelineStatus::Committed => {
+                            report.committed += 1;
+                            report.latencies.push(transaction.submitted_at.elapsed());
+                        }
+                        PipelineStatus::Rejected(reason) => {
+                            if !self.retry(transaction.job, &mut retry_queue, &mut report) {
+                                report.rejected += 1;
+                                report.rejections.push((event.hash, decode(&reason)));
+                            }
+                        }
+                        PipelineStatus::Validating => {
+                            in_flight.insert(event.hash, transaction);
+                        }
+                    }
+                }
+                Ok(Ok(_)) | Err(RecvTimeoutError::Timeout) => {}
+                Ok(Err(error)) => {
+                    return Err(error).wrap_err("The status subscription broke");
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(eyre::eyre!("The status subscription ended"));
+                }
+            }
+
+            // Give up waiting on the transactions past their deadline,
+            // without resubmitting them, since they may still be committed
+            let before = in_flight.len();
+            in_flight
+                .retain(|_, transaction| transaction.submitted_at.elapsed() < self.status_timeout);
+            report.timed_out += (before - in_flight.len()) as u64;
+        }
+
+        report.elapsed = started.elapsed();
+        report.latencies.sort_unstable();
+        Ok(report)
+    }
+
+    /// Sign and submit the transaction of `job`.
+    fn submit(
+        &self,
+        mut job: Pending,
+        in_flight: &mut HashMap<Hash, InFlight>,
+        retry_queue: &mut VecDeque<Pending>,
+        report: &mut LoadReport,
+    ) -> Result<()> {
+        // A fresh transaction each time, since the peer
+        // doesn't accept the same one twice
+        let tx = self
+            .client
+            .build_transaction(job.instructions.clone(), UnlimitedMetadata::new())?;
+        let hash: Hash = tx.hash().into();
+        match self.client.submit_transaction(&tx) {
+            Ok(_) => {
+                report.submitted += 1;
+                job.refusals = 0;
+                in_flight.insert(
+                    hash,
+                    InFlight {
+                        job,
+                        submitted_at: Instant::now(),
+                    },
+                );
+            }
+            Err(error) => {
+                report.refused += 1;
+                job.refusals += 1;
+                if job.refusals > self.config.max_refusals {
+                    // The peer is unreachable, or the error won't go away
+                    report.failed += 1;
+                    report.failures.push(format!("{error:?}"));
+                    return Ok(());
+                }
+                // Back-pressure: try again later, without losing the job
+                job.not_before = Instant::now() + job.backoff;
+                job.backoff = (job.backoff * 2).min(self.config.max_backoff);
+                schedule(retry_queue, job);
+            }
+        }
+        Ok(())
+    }
+
+    /// Schedule `job` for resubmission, if it has retries left.
+    fn retry(
+        &self,
+        mut job: Pending,
+        retry_queue: &mut VecDeque<Pending>,
+        report: &mut LoadReport,
+    ) -> bool {
+        if job.retries >= self.config.max_retries {
+            return false;
+        }
+        job.retries += 1;
+        job.not_before = Instant::now() + job.backoff;
+        job.backoff = (job.backoff * 2).min(self.config.max_backoff);
+        report.retried += 1;
+        schedule(retry_queue, job);
+        true
+    }
+}
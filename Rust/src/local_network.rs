@@ -0,0 +1,202 @@
+//! Several peers started in-process, to rehearse changes to the network.
+//!
+//! [`LocalNetwork::start`] starts `n` peers with [`test_network`] and the
+//! default test genesis, so the examples that work against a single peer work
+//! against it too. [`LocalNetwork::start_peer`] starts one more peer that
+//! trusts the others but isn't part of the network until a `Peer` with its id
+//! is registered; [`LocalNetwork::stop_peer`] stops one after it has been
+//! unregistered.
+//!
+//! Peers agree when they have committed the same number of blocks, see
+//! [`LocalNetwork::wait_until_synced`].
+
+use std::{
+    collections::BTreeMap,
+    fmt, thread,
+    time::{Duration, Instant},
+};
+
+use eyre::{Result, WrapErr};
+use iroha_client::client::Client;
+use iroha_data_model::prelude::*;
+use test_network::{
+    wait_for_genesis_committed, Network, Peer as TestPeer, PeerBuilder, TestClient,
+    TestConfiguration,
+};
+use tokio::runtime::Runtime;
+
+/// How long [`LocalNetwork::wait_until_synced`] waits between two checks.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The peers didn't reach the same height in time.
+#[derive(Debug, Clone)]
+pub struct NotSynced {
+    /// Height each peer was at when the wait ran out.
+    pub heights: Vec<(PeerId, u64)>,
+    /// The least height every peer had to reach.
+    pub min_height: u64,
+}
+
+impl fmt::Display for NotSynced {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the peers didn't agree on a height of at least {}:",
+            self.min_height
+        )?;
+        for (peer, height) in &self.heights {
+            write!(f, " `{peer}` at {height};")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for NotSynced {}
+
+/// Peers running in this process.
+///
+/// Dropping it stops every peer.
+pub struct LocalNetwork {
+    network: Network,
+    // Peers started after the network, whether registered or not
+    joined: BTreeMap<PeerId, TestPeer>,
+    client: Client,
+    // Drives the peers, so it is dropped last
+    runtime: Runtime,
+}
+
+impl LocalNetwork {
+    /// Start `peers` peers and wait for the genesis block to be committed
+    /// on every one of them.
+    ///
+    /// # Errors
+    /// Fails if there are fewer than 2 peers: a single peer is started
+    /// with [`PeerBuilder`] instead.
+    pub fn start(peers: u32) -> Result<Self> {
+        if peers < 2 {
+            return Err(eyre::eyre!(
+                "A local network needs at least 2 peers, not {peers}"
+            ));
+        }
+        let (runtime, network, client) = Network::start_test_with_runtime(peers, None);
+        wait_for_genesis_committed(&network.clients(), 0);
+        Ok(Self {
+            network,
+            joined: BTreeMap::new(),
+            client,
+            runtime,
+        })
+    }
+
+    /// A client of the genesis peer, signing as `alice@wonderland`.
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Ids of the running peers, the genesis peer first.
+    ///
+    /// Includes peers started with [`Self::start_peer`] that aren't
+    /// registered yet.
+    pub fn peer_ids(&self) -> Vec<PeerId> {
+        self.peers().map(|peer| peer.id.clone()).collect()
+    }
+
+    /// A client of the peer `id`, signing as `alice@wonderland`.
+    ///
+    /// # Errors
+    /// Fails if no such peer is running.
+    pub fn peer_client(&self, id: &PeerId) -> Result<Client> {
+        let peer = self
+            .peers()
+            .find(|peer| &peer.id == id)
+            .ok_or_else(|| eyre::eyre!("No peer `{id}` is running"))?;
+        Ok(Client::test(&peer.api_address, &peer.telemetry_address))
+    }
+
+    /// The number of blocks each running peer has committed.
+    ///
+    /// # Errors
+    /// Fails if the status of a peer can't be read.
+    pub fn heights(&self) -> Result<Vec<(PeerId, u64)>> {
+        self.peers()
+            .map(|peer| {
+                let client = Client::test(&peer.api_address, &peer.telemetry_address);
+                let status = client
+                    .get_status()
+                    .wrap_err(format!("Failed to get the status of `{}`", peer.id))?;
+                Ok((peer.id.clone(), status.blocks))
+            })
+            .collect()
+    }
+
+    /// Wait until every running peer has committed the same number of
+    /// blocks, at least `min_height`, and return that number.
+    ///
+    /// # Errors
+    /// Fails with [`NotSynced`] if the peers don't agree within `timeout`.
+    pub fn wait_until_synced(&self, min_height: u64, timeout: Duration) -> Result<u64> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let heights = self.heights()?;
+            let first = heights.first().map_or(0, |(_, height)| *height);
+            if first >= min_height && heights.iter().all(|(_, height)| *height == first) {
+                return Ok(first);
+            }
+            if Instant::now() >= deadline {
+                return Err(NotSynced {
+                    heights,
+                    min_height,
+                }
+                .into());
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Start one more peer that trusts the running ones, and return its id.
+    ///
+    /// The peer doesn't submit a genesis and takes no part in consensus
+    /// until a `Peer` with its id is registered; it then gets the blocks
+    /// from the other peers.
+    pub fn start_peer(&mut self) -> PeerId {
+        let mut config = iroha_config::iroha::Configuration::test();
+        config.sumeragi.trusted_peers.peers = self.peer_ids().into_iter().collect();
+        let peer = self.runtime.block_on(
+            PeerBuilder::new()
+                .with_configuration(config)
+                .without_genesis()
+                .start(),
+        );
+        let id = peer.id.clone();
+        self.joined.insert(id.clone(), peer);
+        id
+    }
+
+    /// Stop the peer `id`.
+    ///
+    /// Unregister it first, otherwise the other peers keep waiting for its
+    /// votes.
+    ///
+    /// # Errors
+    /// Fails if no such peer is running or if `id` is the genesis peer,
+    /// which the client of the network talks to.
+    pub fn stop_peer(&mut self, id: &PeerId) -> Result<()> {
+        if id == &self.network.genesis.id {
+            return Err(eyre::eyre!("The genesis peer `{id}` can't be stopped"));
+        }
+        // Dropping a peer stops it
+        let stopped = self
+            .network
+            .peers
+            .remove(id)
+            .or_else(|| self.joined.remove(id));
+        match stopped {
+            Some(_) => Ok(()),
+            None => Err(eyre::eyre!("No peer `{id}` is running")),
+        }
+    }
+
+    fn peers(&self) -> impl Iterator<Item = &TestPeer> {
+        self.network.peers().chain(self.joined.values())
+    }
+}
@@ -0,0 +1,284 @@
+//! Typed metadata of domains, accounts and asset definitions.
+//!
+//! Metadata is a map from [`Name`]s to [`Value`]s stored on an entity.
+//! [`set`] and [`remove`] submit `SetKeyValueBox` and `RemoveKeyValueBox`,
+//! and [`get`] reads an entry back as a Rust type implementing
+//! [`MetadataValue`], such as [`u32`] or [`String`].
+//!
+//! The entries of a `Store` asset are handled the same way, with the
+//! [`AssetId`] as the [`Owner`]; see [`crate::store`] for whole records.
+//!
+//! The peer limits the number of entries of each entity and the size of
+//! each entry (`WSV.DOMAIN_METADATA_LIMITS`, `WSV.ACCOUNT_METADATA_LIMITS`
+//! and `WSV.ASSET_DEFINITION_METADATA_LIMITS` in the peer configuration).
+//! A `SetKeyValueBox` going over them is rejected.
+
+use std::fmt;
+
+use eyre::{Result, WrapErr};
+use iroha_client::client::{Client, ClientQueryError};
+use iroha_data_model::{
+    prelude::*,
+    query::{
+        account::model::FindAccountKeyValueByIdAndKey,
+        asset::model::{FindAssetDefinitionKeyValueByIdAndKey, FindAssetKeyValueByIdAndKey},
+        domain::model::FindDomainKeyValueByIdAndKey,
+        error::{FindError, QueryExecutionFail},
+    },
+    ValidationFail,
+};
+
+/// Rust type of a metadata value.
+pub trait MetadataValue: Sized {
+    /// Wrap into a [`Value`].
+    fn into_value(self) -> Value;
+
+    /// Extract from `value`, if it holds this type.
+    fn from_value(value: Value) -> Option<Self>;
+}
+
+impl MetadataValue for u32 {
+    fn into_value(self) -> Value {
+        self.to_value()
+    }
+
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::Numeric(NumericValue::U32(value)) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl MetadataValue for u128 {
+    fn into_value(self) -> Value {
+        self.to_value()
+    }
+
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::Numeric(NumericValue::U128(value)) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl MetadataValue for bool {
+    fn into_value(self) -> Value {
+        Value::Bool(self)
+    }
+
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl MetadataValue for String {
+    fn into_value(self) -> Value {
+        Value::String(self)
+    }
+
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl MetadataValue for Value {
+    fn into_value(self) -> Value {
+        self
+    }
+
+    fn from_value(value: Value) -> Option<Self> {
+        Some(value)
+    }
+}
+
+/// An entity that has metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Owner {
+    /// A domain.
+    Domain(DomainId),
+    /// An account.
+    Account(AccountId),
+    /// An asset definition.
+    AssetDefinition(AssetDefinitionId),
+    /// A `Store` asset, whose value is the metadata.
+    Asset(AssetId),
+}
+
+impl From<DomainId> for Owner {
+    fn from(id: DomainId) -> Self {
+        Self::Domain(id)
+    }
+}
+
+impl From<AccountId> for Owner {
+    fn from(id: AccountId) -> Self {
+        Self::Account(id)
+    }
+}
+
+impl From<AssetDefinitionId> for Owner {
+    fn from(id: AssetDefinitionId) -> Self {
+        Self::AssetDefinition(id)
+    }
+}
+
+impl From<AssetId> for Owner {
+    fn from(id: AssetId) -> Self {
+        Self::Asset(id)
+    }
+}
+
+impl From<Owner> for IdBox {
+    fn from(owner: Owner) -> Self {
+        match owner {
+            Owner::Domain(id) => Self::DomainId(id),
+            Owner::Account(id) => Self::AccountId(id),
+            Owner::AssetDefinition(id) => Self::AssetDefinitionId(id),
+            Owner::Asset(id) => Self::AssetId(id),
+        }
+    }
+}
+
+impl fmt::Display for Owner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Domain(id) => write!(f, "domain `{id}`"),
+            Self::Account(id) => write!(f, "account `{id}`"),
+            Self::AssetDefinition(id) => write!(f, "asset definition `{id}`"),
+            Self::Asset(id) => write!(f, "asset `{id}`"),
+        }
+    }
+}
+
+/// A metadata entry holds another type than requested.
+#[derive(Debug, Clone)]
+pub struct WrongType {
+    /// Owner of the entry.
+    pub owner: Owner,
+    /// Key of the entry.
+    pub key: Name,
+    /// The value of the entry.
+    pub value: Value,
+    /// The requested type.
+    pub expected: &'static str,
+}
+
+impl fmt::Display for WrongType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` of {} is {:?}, not a {}",
+            self.key, self.owner, self.value, self.expected
+        )
+    }
+}
+
+impl std::error::Error for WrongType {}
+
+/// Set `key` of `owner` to `value`, adding the entry or replacing its value,
+/// and wait for the transaction to be committed.
+///
+/// # Errors
+/// Fails if the transaction is rejected, e.g. for going over the metadata
+/// limits or for lacking the permission to change the metadata of `owner`.
+pub fn set(
+    client: &Client,
+    owner: impl Into<Owner>,
+    key: Name,
+    value: impl MetadataValue,
+) -> Result<()> {
+    let owner = owner.into();
+    let context = format!("Failed to set `{key}` of {owner}");
+    let set: InstructionBox =
+        SetKeyValueBox::new(IdBox::from(owner), key, value.into_value()).into();
+    client.submit_blocking(set).wrap_err(context)?;
+    Ok(())
+}
+
+/// The value of `key` of `owner`, if there is such an entry.
+///
+/// # Errors
+/// Fails with [`WrongType`] if the value isn't a `T`, and if the query fails
+/// for another reason than a missing entry.
+pub fn get<T: MetadataValue>(
+    client: &Client,
+    owner: impl Into<Owner>,
+    key: &Name,
+) -> Result<Option<T>> {
+    let owner = owner.into();
+    let value = match &owner {
+        Owner::Domain(id) => client.request(FindDomainKeyValueByIdAndKey {
+            id: id.clone().into(),
+            key: key.clone().into(),
+        }),
+        Owner::Account(id) => client.request(FindAccountKeyValueByIdAndKey {
+            id: id.clone().into(),
+            key: key.clone().into(),
+        }),
+        Owner::AssetDefinition(id) => client.request(FindAssetDefinitionKeyValueByIdAndKey {
+            id: id.clone().into(),
+            key: key.clone().into(),
+        }),
+        Owner::Asset(id) => client.request(FindAssetKeyValueByIdAndKey {
+            id: id.clone().into(),
+            key: key.clone().into(),
+        }),
+    };
+    let value = match value {
+        Ok(value) => value,
+        Err(ClientQueryError::Validation(ValidationFail::QueryFailed(
+            QueryExecutionFail::Find(FindError::MetadataKey(_)),
+        ))) => return Ok(None),
+        Err(error) => return Err(error).wrap_err(format!("Failed to query `{key}` of {owner}")),
+    };
+    match T::from_value(value.clone()) {
+        Some(value) => Ok(Some(value)),
+        None => Err(WrongType {
+            owner,
+            key: key.clone(),
+            value,
+            expected: std::any::type_name::<T>(),
+        }
+        .into()),
+    }
+}
+
+/// Replace the value of `key` of `owner` with `update(current)`, where
+/// `current` is `None` if there is no such entry. Returns the new value.
+///
+/// The read and the write are separate, so a concurrent change of the
+/// entry in between is overwritten.
+///
+/// # Errors
+/// Fails if the entry can't be read as a `T` or written.
+pub fn update<T: MetadataValue + Clone>(
+    client: &Client,
+    owner: impl Into<Owner>,
+    key: Name,
+    update: impl FnOnce(Option<T>) -> T,
+) -> Result<T> {
+    let owner = owner.into();
+    let value = update(get(client, owner.clone(), &key)?);
+    set(client, owner, key, value.clone())?;
+    Ok(value)
+}
+
+/// Remove `key` from `owner` and wait for the transaction to be committed.
+///
+/// # Errors
+/// Fails if the transaction is rejected, e.g. because there is no such entry.
+pub fn remove(client: &Client, owner: impl Into<Owner>, key: Name) -> Result<()> {
+    let owner = owner.into();
+    let context = format!("Failed to remove `{key}` of {owner}");
+    let remove: InstructionBox = RemoveKeyValueBox::new(IdBox::from(owner), key).into();
+    client.submit_blocking(remove).wrap_err(context)?;
+    Ok(())
+}
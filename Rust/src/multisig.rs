@@ -0,0 +1,91 @@
+//! Accounts that need several signatures.
+//!
+//! Every account has a signature check condition, evaluated against the
+//! public keys that signed a transaction. By default, one signature of any
+//! signatory of the account is enough. [`m_of_n`] builds a condition that
+//! needs `m` of the given keys instead; only the account itself can set it,
+//! by minting it onto the account.
+//!
+//! A transaction lacking signatures isn't rejected: the peer keeps it in
+//! its queue until its time to live runs out. Submitting the same
+//! transaction again with more signatures, collected with [`cosign`], adds
+//! them to the queued one, which is committed once the condition holds.
+
+use eyre::{Result, WrapErr};
+use iroha_crypto::KeyPair;
+use iroha_data_model::{
+    account::TRANSACTION_SIGNATORIES_VALUE,
+    expression::{ContainsAll, ContextValue, EvaluatesTo, Or},
+    prelude::*,
+};
+
+/// A condition that holds when at least `m` of `keys` signed the transaction.
+///
+/// The condition lists every combination of `m` keys, so keep `keys` short.
+///
+/// # Errors
+/// Fails if `m` is zero or larger than the number of keys.
+pub fn m_of_n(keys: &[PublicKey], m: usize) -> Result<SignatureCheckCondition> {
+    if m == 0 || m > keys.len() {
+        return Err(eyre::eyre!(
+            "Can't require {m} of {} signatures",
+            keys.len()
+        ));
+    }
+
+    let signatories = || -> Result<EvaluatesTo<Vec<Value>>> {
+        let name: Name = TRANSACTION_SIGNATORIES_VALUE.parse()?;
+        Ok(EvaluatesTo::new_unchecked(ContextValue::new(name)))
+    };
+    let mut condition: Option<EvaluatesTo<bool>> = None;
+    for combination in combinations(keys, m) {
+        let required: Vec<Value> = combination.into_iter().map(Value::from).collect();
+        let contains = ContainsAll::new(
+            signatories()?,
+            EvaluatesTo::new_unchecked(Value::Vec(required)),
+        );
+        condition = Some(match condition {
+            None => contains.into(),
+            Some(previous) => Or::new(previous, contains).into(),
+        });
+    }
+    Ok(SignatureCheckCondition(
+        condition.expect("There is at least one combination"),
+    ))
+}
+
+/// The instruction setting the signature check condition of `account`.
+/// It must be signed by the account itself.
+pub fn set_condition(account: AccountId, condition: SignatureCheckCondition) -> InstructionBox {
+    MintBox::new(condition, IdBox::AccountId(account)).into()
+}
+
+/// Add the signature of `key_pair` to `transaction`.
+///
+/// # Errors
+/// Fails if signing fails.
+pub fn cosign(
+    transaction: VersionedSignedTransaction,
+    key_pair: KeyPair,
+) -> Result<VersionedSignedTransaction> {
+    let VersionedSignedTransaction::V1(transaction) = transaction;
+    let transaction = transaction
+        .sign(key_pair)
+        .wrap_err("Failed to sign the transaction")?;
+    Ok(transaction.into())
+}
+
+/// Every way to pick `m` items of `items`, keeping their order.
+fn combinations<T: Clone>(items: &[T], m: usize) -> Vec<Vec<T>> {
+    if m == 0 {
+        return vec![Vec::new()];
+    }
+    let mut result = Vec::new();
+    for (i, first) in items.iter().enumerate() {
+        for mut rest in combinations(&items[i + 1..], m - 1) {
+            rest.insert(0, first.clone());
+            result.push(rest);
+        }
+    }
+    result
+}
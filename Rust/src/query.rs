@@ -0,0 +1,187 @@
+//! Lazily paginated queries.
+//!
+//! [`PagedQuery`] fetches the results of a query one page at a time, so
+//! going through all the accounts of a large ledger doesn't need them all
+//! in memory at once. Pages can be sorted by a metadata key and filtered
+//! with a predicate on the peer.
+//!
+//! Pages are taken at increasing offsets from separate requests. The peer
+//! returns the entities in a stable order (by id, unless sorted), but
+//! entities registered or removed between two requests shift the offsets,
+//! so an entity may then be skipped or repeated.
+//!
+//! Every request asks for one entity more than a page holds, so the last page
+//! is known as such without requesting an empty one after it.
+
+use std::{
+    collections::VecDeque,
+    fmt,
+    num::{NonZeroU32, NonZeroU64},
+};
+
+use eyre::{Result, WrapErr};
+use iroha_client::client::{Client, QueryOutput, QueryResult};
+use iroha_data_model::{
+    predicate::PredicateBox,
+    prelude::*,
+    query::{Pagination, Query, Sorting},
+};
+
+/// Page size used unless another one is requested.
+pub const DEFAULT_PAGE_SIZE: u32 = 100;
+
+/// A query to be fetched page by page.
+#[derive(Debug, Clone)]
+pub struct PagedQuery<Q> {
+    query: Q,
+    page_size: NonZeroU32,
+    sorting: Sorting,
+    filter: PredicateBox,
+}
+
+impl<Q> PagedQuery<Q> {
+    /// Fetch the results of `query` in pages of [`DEFAULT_PAGE_SIZE`].
+    pub fn new(query: Q) -> Self {
+        Self {
+            query,
+            page_size: NonZeroU32::new(DEFAULT_PAGE_SIZE).expect("Not zero"),
+            sorting: Sorting::default(),
+            filter: PredicateBox::default(),
+        }
+    }
+
+    /// Fetch at most `page_size` entities per request.
+    #[must_use]
+    pub fn page_size(mut self, page_size: NonZeroU32) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Sort the entities by the value of the metadata `key`.
+    #[must_use]
+    pub fn sort_by_metadata_key(mut self, key: Name) -> Self {
+        self.sorting = Sorting::by_metadata_key(key);
+        self
+    }
+
+    /// Only return the entities matching `filter`.
+    ///
+    /// The filter is applied by the peer before paginating.
+    #[must_use]
+    pub fn filter(mut self, filter: PredicateBox) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Iterate over the results, requesting the pages on demand.
+    pub fn iter<T>(self, client: &Client) -> Pages<'_, Q, T> {
+        Pages {
+            client,
+            paged: self,
+            offset: 0,
+            pages: 0,
+            buffer: VecDeque::new(),
+            exhausted: false,
+        }
+    }
+}
+
+/// Lazy iterator over the results of a [`PagedQuery`].
+pub struct Pages<'client, Q, T> {
+    client: &'client Client,
+    paged: PagedQuery<Q>,
+    offset: u64,
+    pages: u32,
+    buffer: VecDeque<T>,
+    exhausted: bool,
+}
+
+impl<Q: fmt::Debug, T> fmt::Debug for Pages<'_, Q, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pages")
+            .field("query", &self.paged)
+            .field("offset", &self.offset)
+            .field("pages", &self.pages)
+            .field("buffered", &self.buffer.len())
+            .field("exhausted", &self.exhausted)
+            .finish()
+    }
+}
+
+impl<Q, T> Pages<'_, Q, T>
+where
+    Q: Query<Output = Vec<T>> + Clone + fmt::Debug,
+    Vec<T>: QueryOutput,
+    <Vec<T> as QueryOutput>::Target: IntoIterator<Item = QueryResult<T>>,
+{
+    /// The number of pages requested so far.
+    pub fn pages_fetched(&self) -> u32 {
+        self.pages
+    }
+
+    /// Fetch the next whole page, skipping whatever is left of the current
+    /// one. Returns `None` after the last page.
+    ///
+    /// # Errors
+    /// Fails if the request fails.
+    pub fn next_page(&mut self) -> Option<Result<Vec<T>>> {
+        self.buffer.clear();
+        if self.exhausted {
+            return None;
+        }
+        Some(self.fetch())
+    }
+
+    fn fetch(&mut self) -> Result<Vec<T>> {
+        let page_size = self.paged.page_size.get() as usize;
+        let pagination = Pagination {
+            start: NonZeroU64::new(self.offset),
+            // The extra entity only tells whether there is another page
+            limit: Some(self.paged.page_size.saturating_add(1)),
+        };
+        let mut page = self
+            .client
+            .request_with_filter_and_pagination_and_sorting(
+                self.paged.query.clone(),
+                pagination,
+                self.paged.sorting.clone(),
+                self.paged.filter.clone(),
+            )
+            .wrap_err(format!(
+                "Failed to fetch {:?} at offset {}",
+                self.paged.query, self.offset
+            ))?
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Without the extra entity, this is the last page
+        self.exhausted = page.len() <= page_size;
+        page.truncate(page_size);
+        self.pages += 1;
+        self.offset += page.len() as u64;
+        Ok(page)
+    }
+}
+
+impl<Q, T> Iterator for Pages<'_, Q, T>
+where
+    Q: Query<Output = Vec<T>> + Clone + fmt::Debug,
+    Vec<T>: QueryOutput,
+    <Vec<T> as QueryOutput>::Target: IntoIterator<Item = QueryResult<T>>,
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer.is_empty() && !self.exhausted {
+            match self.fetch() {
+                Ok(page) => self.buffer.extend(page),
+                Err(error) => {
+                    // Don't retry the same page forever
+                    self.exhausted = true;
+                    return Some(Err(error));
+                }
+            }
+        }
+        self.buffer.pop_front().map(Ok)
+    }
+}
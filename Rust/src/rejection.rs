@@ -0,0 +1,75 @@
+//! Readable reasons of rejected transactions.
+//!
+//! A [`PipelineRejectionReason`] nests the reason several levels deep, and
+//! its `Debug` output is hard to read. [`decode`] picks out the errors the
+//! examples care about, such as [`MintabilityError`] and [`MetadataError`],
+//! and keeps the text of the others.
+
+use std::fmt;
+
+use iroha_data_model::{
+    isi::error::{InstructionExecutionError, MintabilityError},
+    metadata::MetadataError,
+    prelude::*,
+    ValidationFail,
+};
+
+/// Why a transaction was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rejection {
+    /// Minting or registering an asset broke the mintability of its definition.
+    Mintability(MintabilityError),
+    /// Setting a metadata entry broke the metadata limits, or its path is invalid.
+    Metadata(MetadataError),
+    /// The signer lacks a permission.
+    NotPermitted(String),
+    /// An instruction failed for another reason.
+    Instruction(String),
+    /// The transaction or its block was rejected before its instructions ran.
+    Other(String),
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mintability(MintabilityError::MintUnmintable) => write!(
+                f,
+                "the asset can't be minted: its definition is not mintable, \
+                 or it is mintable once and has been minted already"
+            ),
+            Self::Mintability(MintabilityError::ForbidMintOnMintable) => write!(
+                f,
+                "minting can only be forbidden on assets that are mintable once"
+            ),
+            Self::Metadata(error) => write!(f, "metadata limits or path broken: {error}"),
+            Self::NotPermitted(reason) => write!(f, "not permitted: {reason}"),
+            Self::Instruction(reason) => write!(f, "instruction failed: {reason}"),
+            Self::Other(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+/// Pick the readable reason out of `reason`.
+pub fn decode(reason: &PipelineRejectionReason) -> Rejection {
+    let PipelineRejectionReason::Transaction(reason) = reason else {
+        return Rejection::Other(format!("{reason:?}"));
+    };
+    match reason {
+        TransactionRejectionReason::Validation(ValidationFail::InstructionFailed(
+            InstructionExecutionError::Mintability(error),
+        )) => Rejection::Mintability(*error),
+        TransactionRejectionReason::Validation(ValidationFail::InstructionFailed(
+            InstructionExecutionError::Metadata(error),
+        )) => Rejection::Metadata(error.clone()),
+        TransactionRejectionReason::Validation(ValidationFail::InstructionFailed(error)) => {
+            Rejection::Instruction(error.to_string())
+        }
+        TransactionRejectionReason::Validation(ValidationFail::NotPermitted(reason)) => {
+            Rejection::NotPermitted(reason.clone())
+        }
+        TransactionRejectionReason::InstructionExecution(fail) => {
+            Rejection::Instruction(fail.reason.clone())
+        }
+        other => Rejection::Other(format!("{other:?}")),
+    }
+}
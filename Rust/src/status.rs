@@ -0,0 +1,218 @@
+//! Following a submitted transaction to its outcome.
+//!
+//! [`submit`] subscribes to the status of a transaction before submitting it
+//! and returns a [`TransactionHandle`], which resolves to an [`Outcome`]:
+//! committed, rejected with the reason decoded, timed out or expired.
+//!
+//! A transaction times out when no final status arrives within
+//! `TRANSACTION_STATUS_TIMEOUT_MS`. It is still in the queue of the peer and
+//! may be committed later, e.g. once it has collected enough signatures.
+//! It expires once `TRANSACTION_TIME_TO_LIVE_MS` has passed since its
+//! creation time: the peer has dropped it and it can't be committed anymore.
+//!
+//! [`TransactionHandle::wait`] stops at the status timeout, so it only sees
+//! a transaction expire if the time to live is the shorter of the two.
+//! [`TransactionHandle::wait_committed`] waits until the transaction expires.
+
+use std::{
+    fmt,
+    sync::mpsc::RecvTimeoutError,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use eyre::{Result, WrapErr};
+use iroha_client::client::Client;
+use iroha_config::client::Configuration;
+use iroha_data_model::{metadata::UnlimitedMetadata, prelude::*};
+
+use crate::{
+    events::{is_final, transaction_status, EventListener, Subscription},
+    rejection::{decode, Rejection},
+};
+
+/// Final status of a transaction.
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    /// The transaction is in a committed block.
+    Committed,
+    /// The transaction was rejected.
+    Rejected(PipelineRejectionReason),
+    /// No final status arrived within the status timeout, but the time to
+    /// live hasn't passed, so the transaction may still be committed.
+    TimedOut,
+    /// The time to live has passed since the transaction was created without
+    /// a final status, so the peer has dropped the transaction.
+    Expired,
+}
+
+impl Outcome {
+    /// The decoded reason, if the transaction was rejected.
+    pub fn rejection(&self) -> Option<Rejection> {
+        match self {
+            Self::Rejected(reason) => Some(decode(reason)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Committed => write!(f, "committed"),
+            Self::Rejected(reason) => write!(f, "rejected: {reason}"),
+            Self::TimedOut => write!(f, "no status in time, but may still be committed"),
+            Self::Expired => write!(f, "expired"),
+        }
+    }
+}
+
+/// A transaction that wasn't committed.
+#[derive(Debug, Clone)]
+pub struct TransactionFailed {
+    /// Hash of the transaction.
+    pub hash: HashOf<VersionedSignedTransaction>,
+    /// What happened to it.
+    pub outcome: Outcome,
+}
+
+impl fmt::Display for TransactionFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "transaction {} {}", self.hash, self.outcome)
+    }
+}
+
+impl std::error::Error for TransactionFailed {}
+
+/// A submitted transaction whose outcome is yet to be seen.
+#[derive(Debug)]
+pub struct TransactionHandle {
+    hash: HashOf<VersionedSignedTransaction>,
+    statuses: Subscription,
+    submitted_at: Instant,
+    status_timeout: Duration,
+    /// Creation time plus time to live, if the time to live is known.
+    expires_at: Option<SystemTime>,
+}
+
+impl TransactionHandle {
+    /// Hash of the transaction.
+    pub fn hash(&self) -> HashOf<VersionedSignedTransaction> {
+        self.hash
+    }
+
+    /// Wait for the final status of the transaction, until the status
+    /// timeout. Returns [`Outcome::Expired`] rather than
+    /// [`Outcome::TimedOut`] only if the transaction has expired by then.
+    ///
+    /// # Errors
+    /// Fails if the status subscription breaks.
+    pub fn wait(self) -> Result<Outcome> {
+        let deadline = self.submitted_at + self.status_timeout;
+        self.wait_until(deadline)
+    }
+
+    /// Wait for the transaction to be committed. A transaction that times
+    /// out may still be committed, so this waits until it expires, if its
+    /// time to live is known.
+    ///
+    /// # Errors
+    /// Fails with [`TransactionFailed`] if it is rejected, expires, or times
+    /// out without a known time to live, and if the status subscription
+    /// breaks.
+    pub fn wait_committed(self) -> Result<HashOf<VersionedSignedTransaction>> {
+        let hash = self.hash;
+        let deadline = match self.expires_at {
+            Some(expires_at) => {
+                let left = expires_at
+                    .duration_since(SystemTime::now())
+                    .unwrap_or(Duration::ZERO);
+                Instant::now() + left
+            }
+            None => self.submitted_at + self.status_timeout,
+        };
+        match self.wait_until(deadline)? {
+            Outcome::Committed => Ok(hash),
+            outcome => Err(TransactionFailed { hash, outcome }.into()),
+        }
+    }
+
+    fn wait_until(self, deadline: Instant) -> Result<Outcome> {
+        loop {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            match self.statuses.recv_timeout(timeout) {
+                Ok(Ok(Event::Pipeline(event))) => match event.status {
+                    PipelineStatus::Committed => return Ok(Outcome::Committed),
+                    PipelineStatus::Rejected(reason) => return Ok(Outcome::Rejected(reason)),
+                    PipelineStatus::Validating => {}
+                },
+                Ok(Ok(_)) => {}
+                Ok(Err(error)) => {
+                    return Err(error).wrap_err(format!(
+                        "The status subscription of transaction {} broke",
+                        self.hash
+                    ));
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    let expired = self
+                        .expires_at
+                        .map_or(false, |expires_at| SystemTime::now() >= expires_at);
+                    return Ok(if expired {
+                        Outcome::Expired
+                    } else {
+                        Outcome::TimedOut
+                    });
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(eyre::eyre!(
+                        "The status subscription of transaction {} ended",
+                        self.hash
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Submit `tx` and follow it with the timeouts of `config`.
+///
+/// # Errors
+/// Fails if the subscription or the submission fails.
+pub fn submit(
+    client: &Client,
+    config: &Configuration,
+    tx: &VersionedSignedTransaction,
+) -> Result<TransactionHandle> {
+    let hash = tx.hash();
+    // Subscribe first, so the status can't be missed,
+    // and stop listening after the final one
+    let statuses = EventListener::new(client.clone(), transaction_status(hash))
+        .into_receiver_until(|event| matches!(event, Event::Pipeline(event) if is_final(event)))?;
+    let submitted_at = Instant::now();
+    client
+        .submit_transaction(tx)
+        .wrap_err(format!("Failed to submit transaction {hash}"))?;
+
+    Ok(TransactionHandle {
+        hash,
+        statuses,
+        submitted_at,
+        status_timeout: Duration::from_millis(config.transaction_status_timeout_ms),
+        expires_at: config.transaction_time_to_live_ms.map(|ttl| {
+            UNIX_EPOCH + tx.payload().creation_time() + Duration::from_millis(ttl.get())
+        }),
+    })
+}
+
+/// Build a transaction of `instructions`, submit it and follow it.
+///
+/// # Errors
+/// Fails if the transaction can't be built or submitted.
+pub fn submit_instructions(
+    client: &Client,
+    config: &Configuration,
+    instructions: impl IntoIterator<Item = InstructionBox>,
+) -> Result<TransactionHandle> {
+    let instructions: Vec<InstructionBox> = instructions.into_iter().collect();
+    let tx = client.build_transaction(instructions, UnlimitedMetadata::new())?;
+    submit(client, config, &tx)
+}
@@ -0,0 +1,156 @@
+//! Rust structs kept in `Store` assets.
+//!
+//! The value of a `Store` asset is a map of key-value entries, like the
+//! metadata of other entities. A [`StoreRecord`] maps a struct to one entry
+//! per field. [`write_record`] sets all of them in a single transaction and
+//! [`read_record`] reads the asset back and rebuilds the struct.
+//!
+//! Single entries are read and written with [`crate::metadata`], using the
+//! [`AssetId`] as the owner.
+
+use std::fmt;
+
+use eyre::{Result, WrapErr};
+use iroha_client::client::Client;
+use iroha_data_model::{metadata::Metadata, prelude::*, query::asset::model::FindAssetById};
+
+use crate::metadata::{MetadataValue, WrongType};
+
+/// A struct stored as the entries of a `Store` asset.
+pub trait StoreRecord: Sized {
+    /// One entry per field.
+    ///
+    /// # Errors
+    /// Fails if a key isn't a valid [`Name`].
+    fn to_entries(&self) -> Result<Vec<(Name, Value)>>;
+
+    /// Rebuild the struct from the entries of an asset.
+    ///
+    /// # Errors
+    /// Fails if an entry is missing or has the wrong type.
+    fn from_entries(entries: &Entries<'_>) -> Result<Self>;
+}
+
+/// An entry a record needs is missing from the asset.
+#[derive(Debug, Clone)]
+pub struct MissingEntry {
+    /// The asset.
+    pub asset: AssetId,
+    /// Key of the entry.
+    pub key: Name,
+}
+
+impl fmt::Display for MissingEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` has no entry `{}`", self.asset, self.key)
+    }
+}
+
+impl std::error::Error for MissingEntry {}
+
+/// The entries of a `Store` asset, read as typed values.
+#[derive(Debug, Clone, Copy)]
+pub struct Entries<'a> {
+    asset: &'a AssetId,
+    metadata: &'a Metadata,
+}
+
+impl<'a> Entries<'a> {
+    /// Wrap the `metadata` of `asset`.
+    pub fn new(asset: &'a AssetId, metadata: &'a Metadata) -> Self {
+        Self { asset, metadata }
+    }
+
+    /// The value of `key`, if there is such an entry.
+    ///
+    /// # Errors
+    /// Fails with [`WrongType`] if the value isn't a `T`.
+    pub fn optional<T: MetadataValue>(&self, key: &str) -> Result<Option<T>> {
+        let key: Name = key.parse()?;
+        let Some(value) = self.metadata.get(&key) else {
+            return Ok(None);
+        };
+        match T::from_value(value.clone()) {
+            Some(value) => Ok(Some(value)),
+            None => Err(WrongType {
+                owner: self.asset.clone().into(),
+                key,
+                value: value.clone(),
+                expected: std::any::type_name::<T>(),
+            }
+            .into()),
+        }
+    }
+
+    /// The value of `key`.
+    ///
+    /// # Errors
+    /// Fails with [`MissingEntry`] if there is no such entry
+    /// and with [`WrongType`] if the value isn't a `T`.
+    pub fn required<T: MetadataValue>(&self, key: &str) -> Result<T> {
+        self.optional(key)?.ok_or_else(|| {
+            MissingEntry {
+                asset: self.asset.clone(),
+                key: key.parse().expect("Parsed by `optional`"),
+            }
+            .into()
+        })
+    }
+}
+
+/// Set an entry of `asset` for every field of `record` in one transaction
+/// and wait for it to be committed. Entries of other fields are kept.
+///
+/// # Errors
+/// Fails if the transaction is rejected.
+pub fn write_record(client: &Client, asset: &AssetId, record: &impl StoreRecord) -> Result<()> {
+    let instructions: Vec<InstructionBox> = record
+        .to_entries()?
+        .into_iter()
+        .map(|(key, value)| SetKeyValueBox::new(IdBox::AssetId(asset.clone()), key, value).into())
+        .collect();
+    client
+        .submit_all_blocking(instructions)
+        .wrap_err(format!("Failed to write a record into `{asset}`"))?;
+    Ok(())
+}
+
+/// Read `asset` and rebuild a record from its entries.
+///
+/// # Errors
+/// Fails if the query fails, the asset isn't a `Store` asset or the record
+/// can't be rebuilt.
+pub fn read_record<R: StoreRecord>(client: &Client, asset: &AssetId) -> Result<R> {
+    let found = client
+        .request(FindAssetById {
+            id: asset.clone().into(),
+        })
+        .wrap_err(format!("Failed to query `{asset}`"))?;
+    let AssetValue::Store(metadata) = &found.value else {
+        return Err(eyre::eyre!(
+            "`{asset}` holds {:?}, not entries",
+            found.value
+        ));
+    };
+    R::from_entries(&Entries::new(asset, metadata))
+}
+
+/// Remove the entries `keys` of `asset` in one transaction and wait for it
+/// to be committed.
+///
+/// # Errors
+/// Fails if the transaction is rejected, e.g. because an entry is missing.
+pub fn remove_entries(
+    client: &Client,
+    asset: &AssetId,
+    keys: impl IntoIterator<Item = Name>,
+) -> Result<()> {
+    let instructions: Vec<InstructionBox> = keys
+        .into_iter()
+        .map(|key| RemoveKeyValueBox::new(IdBox::AssetId(asset.clone()), key).into())
+        .collect();
+    client
+        .submit_all_blocking(instructions)
+        .wrap_err(format!("Failed to remove entries of `{asset}`"))?;
+    Ok(())
+}
@@ -0,0 +1,217 @@
+//! Moving numeric assets between accounts.
+//!
+//! Only numeric assets can be transferred: `Quantity` ([`u32`]), `BigQuantity`
+//! ([`u128`]) and `Fixed` ([`Fixed`]). `Store` assets hold key-value entries
+//! rather than an amount, so there is no [`NumericAsset`] for them and
+//! [`transfer`] doesn't accept them: the peer only transfers numeric assets.
+//! Their entries are handled by [`crate::store`] instead.
+
+use std::fmt;
+
+use eyre::{Result, WrapErr};
+use iroha_client::client::{Client, ClientQueryError};
+use iroha_data_model::{
+    prelude::*,
+    query::{
+        asset::model::FindAssetById,
+        error::{FindError, QueryExecutionFail},
+    },
+    ValidationFail,
+};
+
+/// Rust type of the amount of a numeric asset.
+pub trait NumericAsset: Copy + fmt::Debug + Send + Sync + 'static {
+    /// Value type of the matching asset definitions.
+    const VALUE_TYPE: AssetValueType;
+
+    /// Balance of an account that doesn't hold the asset.
+    fn zero() -> Self;
+
+    /// Extract the amount, if `value` is of [`Self::VALUE_TYPE`].
+    fn from_asset_value(value: &AssetValue) -> Option<Self>;
+
+    /// Wrap the amount into an instruction argument.
+    fn to_value(self) -> Value;
+
+    /// `self - other`, if the result is representable.
+    fn checked_sub(self, other: Self) -> Option<Self>;
+}
+
+impl NumericAsset for u32 {
+    const VALUE_TYPE: AssetValueType = AssetValueType::Quantity;
+
+    fn zero() -> Self {
+        0
+    }
+
+    fn from_asset_value(value: &AssetValue) -> Option<Self> {
+        match value {
+            AssetValue::Quantity(quantity) => Some(*quantity),
+            _ => None,
+        }
+    }
+
+    fn to_value(self) -> Value {
+        Value::Numeric(NumericValue::U32(self))
+    }
+
+    fn checked_sub(self, other: Self) -> Option<Self> {
+        u32::checked_sub(self, other)
+    }
+}
+
+impl NumericAsset for u128 {
+    const VALUE_TYPE: AssetValueType = AssetValueType::BigQuantity;
+
+    fn zero() -> Self {
+        0
+    }
+
+    fn from_asset_value(value: &AssetValue) -> Option<Self> {
+        match value {
+            AssetValue::BigQuantity(quantity) => Some(*quantity),
+            _ => None,
+        }
+    }
+
+    fn to_value(self) -> Value {
+        Value::Numeric(NumericValue::U128(self))
+    }
+
+    fn checked_sub(self, other: Self) -> Option<Self> {
+        u128::checked_sub(self, other)
+    }
+}
+
+impl NumericAsset for Fixed {
+    const VALUE_TYPE: AssetValueType = AssetValueType::Fixed;
+
+    fn zero() -> Self {
+        Fixed::ZERO
+    }
+
+    fn from_asset_value(value: &AssetValue) -> Option<Self> {
+        match value {
+            AssetValue::Fixed(fixed) => Some(*fixed),
+            _ => None,
+        }
+    }
+
+    fn to_value(self) -> Value {
+        Value::Numeric(NumericValue::Fixed(self))
+    }
+
+    fn checked_sub(self, other: Self) -> Option<Self> {
+        Fixed::checked_sub(self, other)
+            .ok()
+            .filter(|difference| !difference.is_negative())
+    }
+}
+
+/// The sender holds less than the requested amount.
+#[derive(Debug, Clone)]
+pub struct InsufficientFunds<T> {
+    /// The asset of the sender.
+    pub asset: AssetId,
+    /// Its balance before the transfer.
+    pub balance: T,
+    /// The requested amount.
+    pub amount: T,
+}
+
+impl<T: fmt::Debug> fmt::Display for InsufficientFunds<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` holds {:?}, which is less than the requested {:?}",
+            self.asset, self.balance, self.amount
+        )
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for InsufficientFunds<T> {}
+
+/// Balances of both parties around a transfer.
+#[derive(Debug, Clone, Copy)]
+pub struct TransferReport<T> {
+    /// Balance of the sender before the transfer.
+    pub sender_before: T,
+    /// Balance of the sender after the transfer.
+    pub sender_after: T,
+    /// Balance of the receiver before the transfer.
+    pub receiver_before: T,
+    /// Balance of the receiver after the transfer.
+    pub receiver_after: T,
+}
+
+/// Balance of `account` in `definition`, zero if it holds none.
+///
+/// # Errors
+/// Fails if the query fails or the asset isn't of type `T`.
+pub fn balance<T: NumericAsset>(
+    client: &Client,
+    definition: &AssetDefinitionId,
+    account: &AccountId,
+) -> Result<T> {
+    let asset_id = AssetId::new(definition.clone(), account.clone());
+    match client.request(FindAssetById {
+        id: asset_id.clone().into(),
+    }) {
+        Ok(asset) => T::from_asset_value(&asset.value).ok_or_else(|| {
+            eyre::eyre!(
+                "`{asset_id}` holds {:?}, expected a {:?} asset",
+                asset.value,
+                T::VALUE_TYPE
+            )
+        }),
+        Err(ClientQueryError::Validation(ValidationFail::QueryFailed(
+            QueryExecutionFail::Find(FindError::Asset(_)),
+        ))) => Ok(T::zero()),
+        Err(error) => Err(error).wrap_err(format!("Failed to query `{asset_id}`")),
+    }
+}
+
+/// Transfer `amount` of `definition` from `sender` to `receiver` and wait
+/// for the transaction to be committed.
+///
+/// The client must be able to sign for `sender`.
+///
+/// # Errors
+/// Fails with [`InsufficientFunds`] before submitting anything if the sender
+/// holds less than `amount`, and otherwise if a query or the transaction fails.
+pub fn transfer<T: NumericAsset>(
+    client: &Client,
+    definition: &AssetDefinitionId,
+    sender: &AccountId,
+    receiver: &AccountId,
+    amount: T,
+) -> Result<TransferReport<T>> {
+    let sender_before = balance::<T>(client, definition, sender)?;
+    let receiver_before = balance::<T>(client, definition, receiver)?;
+
+    let source = AssetId::new(definition.clone(), sender.clone());
+    if sender_before.checked_sub(amount).is_none() {
+        return Err(InsufficientFunds {
+            asset: source,
+            balance: sender_before,
+            amount,
+        }
+        .into());
+    }
+
+    let transfer = TransferBox::new(
+        IdBox::AssetId(source),
+        amount.to_value(),
+        IdBox::AccountId(receiver.clone()),
+    );
+    client
+        .submit_blocking(transfer)
+        .wrap_err("Transfer transaction failed")?;
+
+    Ok(TransferReport {
+        sender_before,
+        sender_after: balance(client, definition, sender)?,
+        receiver_before,
+        receiver_after: balance(client, definition, receiver)?,
+    })
+}
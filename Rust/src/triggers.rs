@@ -0,0 +1,147 @@
+//! Registering, calling and unregistering triggers.
+//!
+//! A trigger runs its instructions on behalf of an account when an event
+//! matching its filter happens. The helpers below register triggers on
+//! behalf of the client's own account.
+
+use std::time::Duration;
+
+use eyre::{Result, WrapErr};
+use iroha_client::client::{Client, ClientQueryError};
+use iroha_data_model::{
+    prelude::*,
+    query::{
+        error::{FindError, QueryExecutionFail},
+        trigger::model::FindTriggerById,
+    },
+    ValidationFail,
+};
+
+/// Register a trigger with any `filter` and wait for it to be committed.
+///
+/// # Errors
+/// Fails if the registration is rejected.
+pub fn register_trigger(
+    client: &Client,
+    id: TriggerId,
+    instructions: impl IntoIterator<Item = InstructionBox>,
+    repeats: Repeats,
+    filter: FilterBox,
+) -> Result<TriggerId> {
+    let instructions: Vec<InstructionBox> = instructions.into_iter().collect();
+    let action = Action::new(instructions, repeats, client.account_id.clone(), filter);
+    let register: InstructionBox = RegisterBox::new(Trigger::new(id.clone(), action)).into();
+    client
+        .submit_blocking(register)
+        .wrap_err(format!("Failed to register trigger `{id}`"))?;
+    Ok(id)
+}
+
+/// Register a trigger that runs every `period`, starting at `start`
+/// (the time since the UNIX epoch).
+///
+/// Time triggers are checked when a block is committed, so they
+/// run no more often than blocks are made.
+///
+/// # Errors
+/// Fails if the registration is rejected.
+pub fn register_time_trigger(
+    client: &Client,
+    id: TriggerId,
+    instructions: impl IntoIterator<Item = InstructionBox>,
+    start: Duration,
+    period: Duration,
+) -> Result<TriggerId> {
+    let schedule = TimeSchedule::starting_at(start).with_period(period);
+    let filter = FilterBox::Time(TimeEventFilter::new(ExecutionTime::Schedule(schedule)));
+    register_trigger(client, id, instructions, Repeats::Indefinitely, filter)
+}
+
+/// Register a trigger that runs whenever the client's account calls it
+/// with [`call_trigger`].
+///
+/// # Errors
+/// Fails if the registration is rejected.
+pub fn register_by_call_trigger(
+    client: &Client,
+    id: TriggerId,
+    instructions: impl IntoIterator<Item = InstructionBox>,
+) -> Result<TriggerId> {
+    let filter = FilterBox::ExecuteTrigger(ExecuteTriggerEventFilter::new(
+        id.clone(),
+        client.account_id.clone(),
+    ));
+    register_trigger(client, id, instructions, Repeats::Indefinitely, filter)
+}
+
+/// Register a trigger that runs on the data events matching `filter`.
+///
+/// # Errors
+/// Fails if the registration is rejected.
+pub fn register_data_trigger(
+    client: &Client,
+    id: TriggerId,
+    instructions: impl IntoIterator<Item = InstructionBox>,
+    filter: DataEventFilter,
+) -> Result<TriggerId> {
+    register_trigger(
+        client,
+        id,
+        instructions,
+        Repeats::Indefinitely,
+        FilterBox::Data(filter),
+    )
+}
+
+/// Data events of accounts being registered in `domain`.
+pub fn account_created_in(domain: DomainId) -> DataEventFilter {
+    let created = AccountFilter::new(
+        FilterOpt::AcceptAll,
+        FilterOpt::BySome(AccountEventFilter::ByCreated),
+    );
+    let domain_filter = DomainFilter::new(
+        FilterOpt::BySome(OriginFilter::new(domain)),
+        FilterOpt::BySome(DomainEventFilter::ByAccount(FilterOpt::BySome(created))),
+    );
+    FilterOpt::BySome(DataEntityFilter::ByDomain(FilterOpt::BySome(domain_filter)))
+}
+
+/// Run the by-call trigger `id` and wait for the call to be committed.
+///
+/// # Errors
+/// Fails if the call is rejected.
+pub fn call_trigger(client: &Client, id: &TriggerId) -> Result<()> {
+    let call: InstructionBox = ExecuteTriggerBox::new(id.clone()).into();
+    client
+        .submit_blocking(call)
+        .wrap_err(format!("Failed to call trigger `{id}`"))?;
+    Ok(())
+}
+
+/// Unregister the trigger `id` and wait for it to be committed.
+///
+/// # Errors
+/// Fails if the unregistration is rejected.
+pub fn unregister_trigger(client: &Client, id: &TriggerId) -> Result<()> {
+    let unregister: InstructionBox = UnregisterBox::new(IdBox::TriggerId(id.clone())).into();
+    client
+        .submit_blocking(unregister)
+        .wrap_err(format!("Failed to unregister trigger `{id}`"))?;
+    Ok(())
+}
+
+/// Whether the trigger `id` is registered.
+///
+/// # Errors
+/// Fails if the query fails for another reason than a missing trigger.
+pub fn trigger_exists(client: &Client, id: &TriggerId) -> Result<bool> {
+    match client.request(FindTriggerById {
+        id: id.clone().into(),
+    }) {
+        Ok(_) => Ok(true),
+        Err(ClientQueryError::Validation(ValidationFail::QueryFailed(
+            QueryExecutionFail::Find(FindError::Trigger(_)),
+        ))) => Ok(false),
+        Err(error) => Err(error).wrap_err(format!("Failed to query trigger `{id}`")),
+    }
+}
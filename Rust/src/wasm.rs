@@ -0,0 +1,89 @@
+//! Building and submitting WASM smart contracts.
+//!
+//! A smart contract is a crate compiled to `wasm32-unknown-unknown`, see
+//! `smart_contracts/mint_rose`. It is submitted in place of the instructions
+//! of a transaction and runs on the peer on behalf of the submitting account.
+
+use std::{fmt, path::Path};
+
+use eyre::{Result, WrapErr};
+use iroha_client::client::Client;
+use iroha_config::client::Configuration;
+use iroha_data_model::{metadata::UnlimitedMetadata, prelude::*, transaction::TransactionLimits};
+use iroha_wasm_builder::Builder;
+
+/// A smart contract larger than the transaction limits allow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooLarge {
+    /// Size of the compiled smart contract.
+    pub size: u64,
+    /// `TRANSACTION_LIMITS.max_wasm_size_bytes` of the configuration.
+    pub limit: u64,
+}
+
+impl fmt::Display for TooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the smart contract takes {} bytes, but at most {} are allowed",
+            self.size, self.limit
+        )
+    }
+}
+
+impl std::error::Error for TooLarge {}
+
+/// Compile and optimize the smart contract crate at `path`.
+///
+/// Needs the `wasm32-unknown-unknown` target to be installed.
+///
+/// # Errors
+/// Fails if the crate doesn't build.
+pub fn build(path: impl AsRef<Path>) -> Result<WasmSmartContract> {
+    let path = path.as_ref();
+    let blob = Builder::new(path)
+        .build()
+        .and_then(|output| output.optimize())
+        .and_then(|output| output.into_bytes())
+        .wrap_err(format!(
+            "Failed to build the smart contract at `{}`",
+            path.display()
+        ))?;
+    Ok(WasmSmartContract::from_compiled(blob))
+}
+
+/// Check `smart_contract` against the transaction `limits`.
+///
+/// # Errors
+/// Fails with [`TooLarge`] if it exceeds `max_wasm_size_bytes`.
+pub fn check_size(smart_contract: &WasmSmartContract, limits: &TransactionLimits) -> Result<u64> {
+    let size = smart_contract.as_ref().len() as u64;
+    if size > limits.max_wasm_size_bytes {
+        return Err(TooLarge {
+            size,
+            limit: limits.max_wasm_size_bytes,
+        }
+        .into());
+    }
+    Ok(size)
+}
+
+/// Submit `smart_contract` as a transaction and wait for it to be committed.
+///
+/// The size is checked against the limits of `config` first, since the
+/// peer rejects a transaction exceeding them anyway.
+///
+/// # Errors
+/// Fails with [`TooLarge`] if the smart contract is too large, and
+/// otherwise if the transaction fails.
+pub fn submit(
+    client: &Client,
+    config: &Configuration,
+    smart_contract: WasmSmartContract,
+) -> Result<HashOf<VersionedSignedTransaction>> {
+    check_size(&smart_contract, &config.transaction_limits)?;
+    let tx = client.build_transaction(smart_contract, UnlimitedMetadata::new())?;
+    client
+        .submit_transaction_blocking(&tx)
+        .wrap_err("Smart contract transaction failed")
+}
@@ -0,0 +1,62 @@
+//! An in-process peer for running the examples without a network.
+
+use std::path::{Path, PathBuf};
+
+use eyre::Result;
+use iroha_2_examples::ConfigLoader;
+use iroha_client::client::Client;
+use iroha_config::client::Configuration;
+use test_network::{wait_for_genesis_committed, Peer as TestPeer, PeerBuilder};
+use tokio::runtime::Runtime;
+
+/// The configuration the examples are written against.
+const BASE_CONFIG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/config.json");
+
+/// A single peer started with the default test genesis.
+///
+/// The genesis registers `alice@wonderland` with the key from `config.json`
+/// and gives her some `rose#wonderland`, which is the world the examples expect.
+pub struct Network {
+    // Keeps the peer alive
+    _runtime: Runtime,
+    peer: TestPeer,
+    client: Client,
+}
+
+impl Network {
+    /// Start the peer and wait for the genesis block to be committed.
+    pub fn start() -> Self {
+        let (runtime, peer, client) = PeerBuilder::new().start_with_runtime();
+        wait_for_genesis_committed(&[client.clone()], 0);
+        Self {
+            _runtime: runtime,
+            peer,
+            client,
+        }
+    }
+
+    /// `config.json` pointed at this peer.
+    pub fn config(&self) -> Result<Configuration> {
+        let api_url = format!("http://{}/", self.peer.api_address);
+        let telemetry_url = format!("http://{}/", self.peer.telemetry_address);
+        let loaded = ConfigLoader::new(Path::new(BASE_CONFIG)).load_from_vars([
+            ("IROHA_TORII_API_URL".to_owned(), api_url),
+            ("IROHA_TORII_TELEMETRY_URL".to_owned(), telemetry_url),
+        ])?;
+        Ok(loaded.config)
+    }
+
+    /// The number of blocks committed so far.
+    pub fn blocks(&self) -> Result<u64> {
+        Ok(self.client.get_status()?.blocks)
+    }
+
+    /// A file path unique to this test run.
+    pub fn temp_path(&self, name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "iroha-2-examples-{}-{}-{name}",
+            std::process::id(),
+            self.peer.api_address.port()
+        ))
+    }
+}
@@ -0,0 +1,130 @@
+//! Checks how the configuration layers are merged, without a peer.
+
+use std::path::{Path, PathBuf};
+
+use eyre::Result;
+use iroha_2_examples::{ConfigLoader, Layer};
+
+const BASE_CONFIG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/config.json");
+
+/// Write a profile file unique to this test run.
+fn profile(name: &str, contents: &str) -> Result<PathBuf> {
+    let path = std::env::temp_dir().join(format!(
+        "iroha-2-examples-{}-{name}.json",
+        std::process::id()
+    ));
+    std::fs::write(&path, contents)?;
+    Ok(path)
+}
+
+fn vars<const N: usize>(vars: [(&str, &str); N]) -> Vec<(String, String)> {
+    vars.into_iter()
+        .map(|(name, value)| (name.to_owned(), value.to_owned()))
+        .collect()
+}
+
+#[test]
+fn later_layers_take_precedence() -> Result<()> {
+    let profile = profile(
+        "precedence",
+        r#"{ "TORII_API_URL": "http://127.0.0.1:8081/", "ACCOUNT_ID": "bob@wonderland" }"#,
+    )?;
+    let loaded = ConfigLoader::new(BASE_CONFIG)
+        .profile(&profile)
+        .load_from_vars(vars([("IROHA_ACCOUNT_ID", "mad_hatter@wonderland")]))?;
+
+    // The profile overrides the base file, the environment overrides both
+    assert_eq!(
+        loaded.config.torii_api_url.to_string(),
+        "http://127.0.0.1:8081/"
+    );
+    assert_eq!(
+        loaded.config.account_id.to_string(),
+        "mad_hatter@wonderland"
+    );
+    assert_eq!(
+        loaded.source_of("TORII_API_URL"),
+        Some(&Layer::Profile(profile.clone()))
+    );
+    assert_eq!(
+        loaded.source_of("ACCOUNT_ID"),
+        Some(&Layer::Env("IROHA_ACCOUNT_ID".to_owned()))
+    );
+    assert_eq!(
+        loaded.source_of("PUBLIC_KEY"),
+        Some(&Layer::Base(Path::new(BASE_CONFIG).to_path_buf()))
+    );
+
+    std::fs::remove_file(profile)?;
+    Ok(())
+}
+
+#[test]
+fn nested_keys_are_separated_by_double_underscores() -> Result<()> {
+    let loaded = ConfigLoader::new(BASE_CONFIG)
+        .load_from_vars(vars([("IROHA_BASIC_AUTH__WEB_LOGIN", "march_hare")]))?;
+
+    // The upper-case name matches the lower-case nested key,
+    // and the sibling keeps coming from the base file
+    assert_eq!(
+        loaded.source_of("BASIC_AUTH.web_login"),
+        Some(&Layer::Env("IROHA_BASIC_AUTH__WEB_LOGIN".to_owned()))
+    );
+    assert_eq!(
+        loaded.source_of("BASIC_AUTH.password"),
+        Some(&Layer::Base(Path::new(BASE_CONFIG).to_path_buf()))
+    );
+    assert!(loaded.source_of("BASIC_AUTH.WEB_LOGIN").is_none());
+
+    Ok(())
+}
+
+#[test]
+fn values_are_parsed_as_json_or_kept_as_strings() -> Result<()> {
+    let loaded = ConfigLoader::new(BASE_CONFIG).load_from_vars(vars([
+        ("IROHA_TRANSACTION_STATUS_TIMEOUT_MS", "3000"),
+        ("IROHA_ADD_TRANSACTION_NONCE", "true"),
+        // Not valid JSON, so taken as a string
+        ("IROHA_TORII_API_URL", "http://127.0.0.1:8082/"),
+        // Valid JSON, but it overrides a string
+        ("IROHA_BASIC_AUTH__PASSWORD", "123456"),
+    ]))?;
+
+    assert_eq!(loaded.config.transaction_status_timeout_ms, 3000);
+    assert!(loaded.config.add_transaction_nonce);
+    assert_eq!(
+        loaded.config.torii_api_url.to_string(),
+        "http://127.0.0.1:8082/"
+    );
+
+    assert_eq!(
+        loaded.source_of("BASIC_AUTH.password"),
+        Some(&Layer::Env("IROHA_BASIC_AUTH__PASSWORD".to_owned()))
+    );
+
+    // A string where a number is expected doesn't make a valid configuration
+    assert!(ConfigLoader::new(BASE_CONFIG)
+        .load_from_vars(vars([("IROHA_TRANSACTION_STATUS_TIMEOUT_MS", "soon")]))
+        .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn unknown_variables_are_not_configuration_keys() -> Result<()> {
+    let loaded = ConfigLoader::new(BASE_CONFIG).load_from_vars(vars([
+        ("IROHA_CONFIG", "/elsewhere/config.json"),
+        ("IROHA_PROFILE", "/elsewhere/profile.json"),
+        ("IROHA_KEY_STORE", "/elsewhere/keys"),
+        ("IROHA_BUILD_NUMBER", "42"),
+        ("IROHA_CI__RUNNER", "linux"),
+        ("PATH", "/usr/bin"),
+    ]))?;
+
+    assert!(loaded
+        .sources
+        .values()
+        .all(|layer| matches!(layer, Layer::Base(_))));
+
+    Ok(())
+}
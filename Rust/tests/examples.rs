@@ -0,0 +1,466 @@
+//! Runs the examples against an in-process peer.
+//!
+//! The examples are included as modules, so the scenarios they implement are
+//! exercised without a running network and without `config.json` edits.
+
+mod common;
+
+use common::Network;
+use eyre::Result;
+
+#[allow(dead_code)]
+#[path = "../examples/apply_fixture.rs"]
+mod apply_fixture;
+#[allow(dead_code)]
+#[path = "../examples/block_archive.rs"]
+mod block_archive;
+#[allow(dead_code)]
+#[path = "../examples/client_account_definition.rs"]
+mod client_account_definition;
+#[allow(dead_code)]
+#[path = "../examples/client_account_registration.rs"]
+mod client_account_registration;
+#[allow(dead_code)]
+#[path = "../examples/client_asset_burning.rs"]
+mod client_asset_burning;
+#[allow(dead_code)]
+#[path = "../examples/client_asset_minting.rs"]
+mod client_asset_minting;
+#[allow(dead_code)]
+#[path = "../examples/client_asset_registration.rs"]
+mod client_asset_registration;
+#[allow(dead_code)]
+#[path = "../examples/client_batch_submission.rs"]
+mod client_batch_submission;
+#[allow(dead_code)]
+#[path = "../examples/client_domain_registration.rs"]
+mod client_domain_registration;
+#[allow(dead_code)]
+#[path = "../examples/client_event_subscription.rs"]
+mod client_event_subscription;
+#[allow(dead_code)]
+#[path = "../examples/client_json_config.rs"]
+mod client_json_config;
+#[allow(dead_code)]
+#[path = "../examples/client_key_store.rs"]
+mod client_key_store;
+#[allow(dead_code)]
+#[path = "../examples/client_mintability.rs"]
+mod client_mintability;
+#[allow(dead_code)]
+#[path = "../examples/client_multiple_identities.rs"]
+mod client_multiple_identities;
+#[allow(dead_code)]
+#[path = "../examples/client_multisig.rs"]
+mod client_multisig;
+#[allow(dead_code)]
+#[path = "../examples/client_paginated_queries.rs"]
+mod client_paginated_queries;
+#[allow(dead_code)]
+#[path = "../examples/client_smart_contract.rs"]
+mod client_smart_contract;
+#[allow(dead_code)]
+#[path = "../examples/client_store_asset.rs"]
+mod client_store_asset;
+#[allow(dead_code)]
+#[path = "../examples/genesis_generator.rs"]
+mod genesis_generator;
+#[allow(dead_code)]
+#[path = "../examples/ledger_drift.rs"]
+mod ledger_drift;
+#[allow(dead_code)]
+#[path = "../examples/load_generator.rs"]
+mod load_generator;
+#[allow(dead_code)]
+#[path = "../examples/peer_membership.rs"]
+mod peer_membership;
+#[allow(dead_code)]
+#[path = "../examples/tutorial-10-data-trigger.rs"]
+mod tutorial_10_data_trigger;
+#[allow(dead_code)]
+#[path = "../examples/tutorial-11-metadata.rs"]
+mod tutorial_11_metadata;
+#[allow(dead_code)]
+#[path = "../examples/tutorial-3-register-domain.rs"]
+mod tutorial_3_register_domain;
+#[allow(dead_code)]
+#[path = "../examples/tutorial-4-register-account.rs"]
+mod tutorial_4_register_account;
+#[allow(dead_code)]
+#[path = "../examples/tutorial-5-register-mint-burn-asset.rs"]
+mod tutorial_5_register_mint_burn_asset;
+#[allow(dead_code)]
+#[path = "../examples/tutorial-6-transfer-asset.rs"]
+mod tutorial_6_transfer_asset;
+#[allow(dead_code)]
+#[path = "../examples/tutorial-7-roles-and-permissions.rs"]
+mod tutorial_7_roles_and_permissions;
+#[allow(dead_code)]
+#[path = "../examples/tutorial-8-time-trigger.rs"]
+mod tutorial_8_time_trigger;
+#[allow(dead_code)]
+#[path = "../examples/tutorial-9-by-call-trigger.rs"]
+mod tutorial_9_by_call_trigger;
+
+#[test]
+fn account_definition() -> Result<()> {
+    client_account_definition::account_definition_test()
+}
+
+#[test]
+fn genesis_generator() -> Result<()> {
+    let output = std::env::temp_dir().join(format!(
+        "iroha-2-examples-{}-genesis.json",
+        std::process::id()
+    ));
+    let mut args: Vec<String> = [
+        "--domains=3",
+        "--accounts-per-domain",
+        "2",
+        "--initial-balance=10",
+        "--key-seed=test-",
+        "--role=auditor",
+    ]
+    .map(str::to_owned)
+    .into();
+    args.extend(["--output".to_owned(), output.display().to_string()]);
+    let options =
+        genesis_generator::Options::from_args(iroha_2_examples::harness::Args::new(args))?;
+    genesis_generator::generate(&options)?;
+
+    // The streamed genesis reads back as a whole block
+    // and holds the same as the one built in memory
+    let genesis: iroha_genesis::RawGenesisBlock =
+        serde_json::from_reader(std::io::BufReader::new(std::fs::File::open(&output)?))?;
+    let built = options.spec.build()?;
+    assert_eq!(
+        serde_json::to_value(&genesis)?,
+        serde_json::to_value(&built)?
+    );
+    let instructions: usize = genesis.transactions.iter().map(|tx| tx.isi.len()).sum();
+    // 3 domains, 6 accounts, 3 asset definitions, 1 role, 6 grants, 6 mints
+    assert_eq!(instructions, 3 + 6 + 3 + 1 + 6 + 6);
+    std::fs::remove_file(&output)?;
+
+    Ok(())
+}
+
+#[test]
+fn tutorials() -> Result<()> {
+    let network = Network::start();
+    let config = network.config()?;
+
+    // Each tutorial builds on the state left by the previous one
+    tutorial_3_register_domain::register_domain(&config)?;
+    tutorial_4_register_account::register_account(&config)?;
+    tutorial_5_register_mint_burn_asset::register_mint_burn_asset(&config)?;
+    tutorial_6_transfer_asset::transfer_assets(&config)?;
+    tutorial_7_roles_and_permissions::roles_and_permissions(&config)?;
+    tutorial_8_time_trigger::time_trigger(&config)?;
+    tutorial_9_by_call_trigger::by_call_trigger(&config)?;
+    tutorial_10_data_trigger::data_trigger(&config)?;
+    tutorial_11_metadata::metadata_tutorial(&config)?;
+
+    // Registration tutorials can be run again
+    tutorial_3_register_domain::register_domain(&config)?;
+    tutorial_4_register_account::register_account(&config)?;
+
+    Ok(())
+}
+
+#[test]
+fn ensure_reports_differences() -> Result<()> {
+    use iroha_2_examples::ensure::{ensure_asset_definition, ensure_domain, Ensured};
+    use iroha_data_model::prelude::*;
+
+    let network = Network::start();
+    let client = iroha_client::client::Client::new(&network.config()?)?;
+
+    let domain: DomainId = "ensured".parse()?;
+    assert_eq!(
+        ensure_domain(&client, Domain::new(domain.clone()))?,
+        Ensured::Registered
+    );
+    assert_eq!(
+        ensure_domain(&client, Domain::new(domain.clone()))?,
+        Ensured::Exists
+    );
+
+    let definition: AssetDefinitionId = "coin#ensured".parse()?;
+    assert_eq!(
+        ensure_asset_definition(&client, AssetDefinition::quantity(definition.clone()))?,
+        Ensured::Registered
+    );
+    let Ensured::Differs(differences) =
+        ensure_asset_definition(&client, AssetDefinition::fixed(definition).mintable_once())?
+    else {
+        panic!("The definition should differ");
+    };
+    let fields: Vec<&str> = differences.iter().map(|d| d.field.as_str()).collect();
+    assert_eq!(fields, ["value_type", "mintable"]);
+
+    Ok(())
+}
+
+#[test]
+fn client_examples() -> Result<()> {
+    let network = Network::start();
+    let config = network.config()?;
+
+    client_json_config::json_config_client_test(&config)?;
+
+    // The examples keep their keys out of the crate directory
+    let keys = network.temp_path("keys");
+    let store = iroha_2_examples::keys::KeyStore::open(&keys)?;
+
+    // Each example waits for its transactions, so the next one sees their effects
+    client_domain_registration::domain_registration_test(&config)?;
+    client_account_registration::account_registration_test(&config)?;
+    client_asset_registration::asset_registration_test(&config)?;
+    client_asset_minting::asset_minting_test(&config)?;
+    client_asset_burning::asset_burning_test(&config)?;
+    client_event_subscription::event_subscription_test(&config)?;
+    client_paginated_queries::paginated_queries_test(&config)?;
+    client_batch_submission::batch_submission_test(&config)?;
+    client_multisig::multisig_test(&config)?;
+    // A new shared account is registered the second time
+    client_multisig::multisig_test(&config)?;
+    client_store_asset::store_asset_test(&config)?;
+    // The certificate is written again over the incomplete entries
+    client_store_asset::store_asset_test(&config)?;
+
+    client_key_store::key_store_test(&config, &store)?;
+    // The stored key still signs on the second run
+    client_key_store::key_store_test(&config, &store)?;
+    std::fs::remove_dir_all(keys)?;
+
+    Ok(())
+}
+
+// Building the smart contract fetches its dependencies
+// and needs the `wasm32-unknown-unknown` target
+#[test]
+#[ignore = "needs network access and the wasm32-unknown-unknown target"]
+fn smart_contract() -> Result<()> {
+    let network = Network::start();
+    client_smart_contract::smart_contract_test(&network.config()?)
+}
+
+#[test]
+fn block_archive_resumes() -> Result<()> {
+    let network = Network::start();
+    let config = network.config()?;
+    // Commit one more block after the genesis
+    client_event_subscription::event_subscription_test(&config)?;
+    let blocks = network.blocks()?;
+
+    let mut options = block_archive::Options {
+        archive: network.temp_path("blocks.jsonl"),
+        from: 1.try_into()?,
+        resume: false,
+        count: Some(blocks - 1),
+    };
+    block_archive::block_archive_test(&config, &options)?;
+
+    // Resuming picks up the last block and checks it against the archived ones
+    options.resume = true;
+    options.count = Some(1);
+    block_archive::block_archive_test(&config, &options)?;
+
+    let archive = iroha_2_examples::archive::BlockArchive::open(&options.archive)?;
+    assert_eq!(archive.tip().map(|tip| tip.height), Some(blocks));
+    std::fs::remove_file(&options.archive)?;
+
+    Ok(())
+}
+
+#[test]
+fn load_generator_finishes_every_job() -> Result<()> {
+    let network = Network::start();
+    let config = network.config()?;
+
+    let options = load_generator::Options {
+        transactions: 20,
+        first: 0,
+        load: iroha_2_examples::load::LoadConfig {
+            max_in_flight: 4,
+            ..Default::default()
+        },
+    };
+    let report = load_generator::load_generator_test(&config, &options)?;
+    assert_eq!(report.committed, 20);
+
+    // Registering the same entities again is rejected, retries included
+    let report = load_generator::load_generator_test(&config, &options)?;
+    assert_eq!(report.rejected, 20);
+    assert_eq!(report.rejections.len(), 20);
+    assert_eq!(report.retried, 20 * u64::from(options.load.max_retries));
+
+    Ok(())
+}
+
+#[test]
+fn fixture_applies_once() -> Result<()> {
+    let network = Network::start();
+    let config = network.config()?;
+    let fixture = iroha_2_examples::fixture::Fixture::from_path(apply_fixture::DEFAULT_FIXTURE)?;
+
+    let report = apply_fixture::apply_fixture(&config, &fixture)?;
+    assert!(report
+        .accounts
+        .iter()
+        .all(|(_, ensured)| ensured.is_registered()));
+    assert_eq!(report.minted.len(), 3);
+    assert_eq!(report.granted.len(), 1);
+
+    // Everything is in place, so nothing is submitted the second time
+    let report = apply_fixture::apply_fixture(&config, &fixture)?;
+    assert!(!report
+        .accounts
+        .iter()
+        .any(|(_, ensured)| ensured.is_registered()));
+    assert!(report.minted.is_empty() && report.granted.is_empty());
+    assert_eq!(report.differences().count(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn ledger_drift_after_changes() -> Result<()> {
+    use iroha_2_examples::drift::{Change, Entity};
+    use iroha_data_model::prelude::*;
+
+    let network = Network::start();
+    let config = network.config()?;
+    let options = ledger_drift::Options {
+        fixture: apply_fixture::DEFAULT_FIXTURE.into(),
+        ignored: vec!["genesis".parse()?, "wonderland".parse()?],
+        format: ledger_drift::Format::Json,
+    };
+    let fixture = iroha_2_examples::fixture::Fixture::from_path(&options.fixture)?;
+    apply_fixture::apply_fixture(&config, &fixture)?;
+    assert!(ledger_drift::ledger_drift(&config, &options)?.is_empty());
+
+    // Mint more tea and register an account the fixture doesn't know
+    let client = iroha_client::client::Client::new(&config)?;
+    let tea: AssetId = "tea##march_hare@looking_glass".parse()?;
+    let cheshire_cat: AccountId = "cheshire_cat@looking_glass".parse()?;
+    let changes: [InstructionBox; 2] = [
+        MintBox::new(1_u32.to_value(), IdBox::AssetId(tea.clone())).into(),
+        RegisterBox::new(Account::new(
+            cheshire_cat.clone(),
+            [config.public_key.clone()],
+        ))
+        .into(),
+    ];
+    client.submit_all_blocking(changes)?;
+
+    let drift = ledger_drift::ledger_drift(&config, &options)?;
+    assert_eq!(
+        drift.changes,
+        [
+            Change::Balance {
+                id: tea.to_string(),
+                expected: "50".to_owned(),
+                actual: "51".to_owned(),
+            },
+            Change::Extra {
+                entity: Entity::Account,
+                id: cheshire_cat.to_string(),
+            },
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn key_store_keeps_keys_private() -> Result<()> {
+    use iroha_2_examples::keys::{generate, KeyStore};
+    use iroha_crypto::Algorithm;
+
+    let dir = std::env::temp_dir().join(format!("iroha-2-examples-{}-keys", std::process::id()));
+    let store = KeyStore::open(&dir)?;
+
+    let key_pair = store.generate("white_rabbit@looking_glass", Algorithm::Secp256k1)?;
+    let loaded = store.load("white_rabbit@looking_glass")?;
+    assert_eq!(loaded.public_key(), key_pair.public_key());
+    assert_eq!(store.aliases()?, ["white_rabbit@looking_glass"]);
+    assert!(store
+        .save("white_rabbit@looking_glass", &generate(Algorithm::Ed25519)?)
+        .is_err());
+    assert!(store.path("../alice@wonderland").is_err());
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = store.path("white_rabbit@looking_glass")?;
+        assert_eq!(
+            std::fs::metadata(&path)?.permissions().mode() & 0o777,
+            0o600
+        );
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644))?;
+        assert!(store.load("white_rabbit@looking_glass").is_err());
+    }
+
+    std::fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[test]
+fn multiple_identities() -> Result<()> {
+    let network = Network::start();
+    let config = network.config()?;
+
+    client_multiple_identities::multiple_identities_test(&config)?;
+    // The white rabbit signs with the same key pair the second time too
+    client_multiple_identities::multiple_identities_test(&config)?;
+
+    Ok(())
+}
+
+#[test]
+fn mintability_is_enforced() -> Result<()> {
+    use iroha_2_examples::rejection::Rejection;
+    use iroha_data_model::isi::error::MintabilityError;
+
+    let network = Network::start();
+    let config = network.config()?;
+
+    // The same rejections when run again on the same network
+    for _ in 0..2 {
+        let [second_medal_mint, relic_mint] = client_mintability::mintability_test(&config)?;
+        assert_eq!(
+            second_medal_mint,
+            Rejection::Mintability(MintabilityError::MintUnmintable)
+        );
+        assert_eq!(
+            relic_mint,
+            Rejection::Mintability(MintabilityError::MintUnmintable)
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn peer_membership_changes() -> Result<()> {
+    let options = peer_membership::Options {
+        peers: 4,
+        timeout: std::time::Duration::from_secs(60),
+    };
+    let mut network = iroha_2_examples::local_network::LocalNetwork::start(options.peers)?;
+
+    let height = peer_membership::peer_membership(&mut network, &options)?;
+
+    // The genesis, the registration, the unregistration and a domain
+    assert!(height >= 4);
+    assert_eq!(network.peer_ids().len(), 4);
+    let heights = network.heights()?;
+    assert!(heights
+        .iter()
+        .all(|(_, peer_height)| *peer_height == height));
+
+    Ok(())
+}